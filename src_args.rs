@@ -0,0 +1,113 @@
+// Проверка количества и типов аргументов команд.
+// Сигнатура записывается так же, как в сообщении об ошибке:
+//   <name>           обязательный аргумент
+//   [name]           необязательный аргумент, только в конце
+//   <name:usize>     аргумент с типом: usize или i64
+//   <name:usize|all> несколько вариантов: тип или ключевое слово
+//   <name...>        все оставшиеся слова, хотя бы одно; [name...] — ноль и более
+
+struct Param<'a> {
+    optional: bool,
+    rest: bool,
+    // Допустимые варианты: типы и ключевые слова; пусто — любое слово
+    choices: Vec<&'a str>,
+}
+
+fn parse_param(param: &str) -> Param<'_> {
+    let optional = param.starts_with('[');
+    let inner = &param[1..param.len() - 1];
+    let (inner, rest) = match inner.strip_suffix("...") {
+        Some(inner) => (inner, true),
+        None => (inner, false),
+    };
+    let choices = match inner.split_once(':') {
+        Some((_, choices)) => choices.split('|').collect(),
+        None => Vec::new(),
+    };
+    Param { optional, rest, choices }
+}
+
+fn matches(choice: &str, arg: &str) -> bool {
+    match choice {
+        "usize" => arg.parse::<usize>().is_ok(),
+        "i64" => arg.parse::<i64>().is_ok(),
+        keyword => arg.eq_ignore_ascii_case(keyword),
+    }
+}
+
+// Проверяет аргументы (без имени команды) по сигнатуре
+pub fn validate(command: &str, signature: &str, args: &[&str]) -> Result<(), String> {
+    let error = || {
+        if signature.is_empty() {
+            format!("{} takes no arguments", command)
+        } else {
+            format!("{} expects {}", command, signature)
+        }
+    };
+
+    let mut args = args.iter();
+    for param in signature.split_whitespace().map(parse_param) {
+        let Some(arg) = args.next() else {
+            if param.optional {
+                return Ok(());
+            }
+            return Err(error());
+        };
+        let accepts = |arg: &str| param.choices.is_empty() || param.choices.iter().any(|choice| matches(choice, arg));
+        if !accepts(arg) {
+            return Err(error());
+        }
+        if param.rest {
+            return if args.all(|arg| accepts(arg)) { Ok(()) } else { Err(error()) };
+        }
+    }
+
+    // Лишние аргументы в конце
+    match args.next() {
+        Some(_) => Err(error()),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate;
+
+    #[test]
+    fn counts_required_and_optional_arguments() {
+        assert_eq!(validate("SLEN", "", &[]), Ok(()));
+        assert_eq!(validate("SLEN", "", &["x"]), Err("SLEN takes no arguments".to_string()));
+
+        let signature = "<value> [count]";
+        assert_eq!(validate("CMD", signature, &["a"]), Ok(()));
+        assert_eq!(validate("CMD", signature, &["a", "b"]), Ok(()));
+        assert_eq!(validate("CMD", signature, &[]), Err("CMD expects <value> [count]".to_string()));
+        assert_eq!(validate("CMD", signature, &["a", "b", "c"]), Err("CMD expects <value> [count]".to_string()));
+    }
+
+    #[test]
+    fn checks_types_and_keywords() {
+        assert!(validate("MGET", "<index:usize>", &["3"]).is_ok());
+        assert!(validate("MGET", "<index:usize>", &["-3"]).is_err());
+        assert!(validate("MGET", "<index:usize>", &["x"]).is_err());
+        assert!(validate("PQPUSH", "<priority:i64> <value>", &["-3", "a"]).is_ok());
+        assert!(validate("PQPUSH", "<priority:i64> <value>", &["99999999999999999999", "a"]).is_err());
+
+        let signature = "[capacity:usize|unbounded] [policy:overwrite|reject]";
+        assert!(validate("QCAPACITY", signature, &[]).is_ok());
+        assert!(validate("QCAPACITY", signature, &["UNBOUNDED"]).is_ok());
+        assert!(validate("QCAPACITY", signature, &["4", "Reject"]).is_ok());
+        assert!(validate("QCAPACITY", signature, &["many"]).is_err());
+        assert!(validate("QCAPACITY", signature, &["4", "drop"]).is_err());
+    }
+
+    #[test]
+    fn rest_parameters_take_remaining_words() {
+        assert!(validate("EVAL", "<expression...>", &["1", "+", "2"]).is_ok());
+        assert_eq!(validate("EVAL", "<expression...>", &[]), Err("EVAL expects <expression...>".to_string()));
+        assert!(validate("CMD", "<name> [values...]", &["a"]).is_ok());
+        assert!(validate("CMD", "<name> [values...]", &["a", "b", "c"]).is_ok());
+        assert!(validate("SUM", "<values:i64...>", &["1", "-2", "3"]).is_ok());
+        assert!(validate("SUM", "<values:i64...>", &["1", "x"]).is_err());
+    }
+}
@@ -0,0 +1,57 @@
+// Динамический массив
+pub struct Array {
+    data: Vec<String>,
+}
+
+impl Array {
+    pub fn new(capacity: usize) -> Self {
+        Array {
+            data: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn add_back(&mut self, value: String) {
+        self.data.push(value);
+    }
+
+    // При index == len добавляет в конец
+    pub fn add_index(&mut self, index: usize, value: String) -> Result<(), String> {
+        if index > self.data.len() {
+            return Err("Index out of range".to_string());
+        }
+        self.data.insert(index, value);
+        Ok(())
+    }
+
+    pub fn remove_index(&mut self, index: usize) -> Result<String, String> {
+        if index >= self.data.len() {
+            return Err("Index out of range".to_string());
+        }
+        Ok(self.data.remove(index))
+    }
+
+    pub fn get_index(&self, index: usize) -> Result<&String, String> {
+        self.data
+            .get(index)
+            .ok_or_else(|| "Index out of range".to_string())
+    }
+
+    pub fn replace_index(&mut self, index: usize, value: String) -> Result<String, String> {
+        match self.data.get_mut(index) {
+            Some(current) => Ok(std::mem::replace(current, value)),
+            None => Err("Index out of range".to_string()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, String> {
+        self.data.iter()
+    }
+}
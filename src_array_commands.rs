@@ -0,0 +1,138 @@
+use crate::array::Array;
+use crate::output::{CommandResult, ErrorKind};
+use crate::registry::{restore_command, Call, CommandSpec, DataFile, DataStructures, Module};
+
+// Команды динамического массива
+pub struct ArrayModule {
+    array: Array,
+}
+
+impl ArrayModule {
+    pub fn new() -> Self {
+        ArrayModule { array: Array::new(10) }
+    }
+
+    fn restore_command(&self) -> String {
+        restore_command("array", self.array.iter().cloned())
+    }
+}
+
+const COMMANDS: &[CommandSpec] = &[
+    CommandSpec { name: "MPUSH_BACK", signature: "<value>", help: "Append a value", lookup: false },
+    CommandSpec { name: "MPUSH_INDEX", signature: "<index:usize> <value>", help: "Insert a value at an index", lookup: false },
+    CommandSpec { name: "MDEL", signature: "<index:usize>", help: "Remove the element at an index", lookup: false },
+    CommandSpec { name: "MGET", signature: "<index:usize>", help: "Show the element at an index", lookup: true },
+    CommandSpec { name: "MREPLACE", signature: "<index:usize> <value>", help: "Replace the element at an index", lookup: false },
+    CommandSpec { name: "MLEN", signature: "", help: "Show the array length", lookup: true },
+    CommandSpec { name: "MPRINT", signature: "", help: "Print the array", lookup: true },
+];
+
+const FILES: &[DataFile] = &[DataFile { suffix: "array", description: Some("array") }];
+
+impl Module for ArrayModule {
+    fn title(&self) -> &'static str {
+        "Array"
+    }
+
+    fn name(&self) -> &'static str {
+        "array"
+    }
+
+    fn commands(&self) -> &'static [CommandSpec] {
+        COMMANDS
+    }
+
+    fn execute(&mut self, call: &Call, ds: &mut DataStructures, out: &mut CommandResult) {
+        let (query, parts) = (call.query, call.parts);
+        match call.command {
+            "MPUSH_BACK" => {
+                self.array.add_back(parts[1].to_string());
+                out.line(format!("Added \"{}\" to array", parts[1]));
+                ds.record(query, vec![format!("MDEL {}", self.array.len() - 1)]);
+            }
+            "MPUSH_INDEX" => {
+                let index = call.number::<usize>(1);
+                match self.array.add_index(index, parts[2].to_string()) {
+                    Ok(_) => {
+                        out.line(format!("Added \"{}\" at index {}", parts[2], index));
+                        ds.record(query, vec![format!("MDEL {}", index)]);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::NotFound, e),
+                }
+            }
+            "MDEL" => {
+                let index = call.number::<usize>(1);
+                let old = self.array.get_index(index).cloned();
+                match self.array.remove_index(index) {
+                    Ok(_) => {
+                        out.line(format!("Removed element at index {}", index));
+                        if let Ok(old) = old {
+                            ds.record(query, vec![format!("MPUSH_INDEX {} {}", index, old)]);
+                        }
+                    }
+                    Err(e) => out.fail_as(ErrorKind::NotFound, e),
+                }
+            }
+            "MGET" => {
+                let index = call.number::<usize>(1);
+                match self.array.get_index(index) {
+                    Ok(value) => {
+                        out.line(format!("Array[{}] = \"{}\"", index, value));
+                        out.set_value(value);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::NotFound, e),
+                }
+            }
+            "MREPLACE" => {
+                let index = call.number::<usize>(1);
+                let old = self.array.get_index(index).cloned();
+                match self.array.replace_index(index, parts[2].to_string()) {
+                    Ok(_) => {
+                        out.line(format!("Replaced array[{}] with \"{}\"", index, parts[2]));
+                        if let Ok(old) = old {
+                            ds.record(query, vec![format!("MREPLACE {} {}", index, old)]);
+                        }
+                    }
+                    Err(e) => out.fail_as(ErrorKind::NotFound, e),
+                }
+            }
+            "MLEN" => {
+                out.line(format!("Array length: {}", self.array.len()));
+                out.set_value(self.array.len());
+            }
+            "MPRINT" => {
+                let values: Vec<&String> = self.array.iter().collect();
+                out.line(format!("Array: {}", values.iter().map(|value| format!("{} ", value)).collect::<String>()));
+                out.set_values(values);
+            }
+            _ => unreachable!("{} is not an array command", call.command),
+        }
+    }
+
+    fn files(&self) -> &'static [DataFile] {
+        FILES
+    }
+
+    fn load(&mut self, _file: &str, lines: &[String]) -> Vec<String> {
+        for value in lines.iter().flat_map(|line| line.split_whitespace()) {
+            self.array.add_back(value.to_string());
+        }
+        Vec::new()
+    }
+
+    fn save(&self, _file: &str) -> String {
+        self.array.iter().map(|value| format!("{} ", value)).collect()
+    }
+
+    fn snapshot(&self) -> Option<String> {
+        Some(self.restore_command())
+    }
+
+    fn restore(&mut self, _target: &str, values: &[&str]) -> Result<(), String> {
+        self.array = Array::new(10);
+        for value in values {
+            self.array.add_back(value.to_string());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,224 @@
+use std::fmt;
+
+use crate::stack::Stack;
+
+// Проверка баланса скобок ()[]{}<> и простых XML-тегов на основе стека.
+// "<" перед буквой или "/" начинает тег, иначе это угловая скобка.
+// Позиции считаются в символах с нуля.
+
+#[derive(Debug, PartialEq)]
+pub struct BalanceError {
+    pub position: usize,
+    // Ожидавшийся закрывающий элемент; None, если закрывать было нечего
+    pub expected: Option<String>,
+    // Найденный элемент; None для конца текста
+    pub found: Option<String>,
+    // Позиция открывающего элемента, который ожидал закрытия
+    pub opened_at: Option<usize>,
+}
+
+impl fmt::Display for BalanceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.expected, &self.found) {
+            (Some(expected), Some(found)) => write!(
+                f,
+                "Mismatch at position {}: expected \"{}\" (opened at position {}) but found \"{}\"",
+                self.position,
+                expected,
+                self.opened_at.unwrap_or(0),
+                found
+            ),
+            (Some(expected), None) => write!(
+                f,
+                "Unclosed element opened at position {}: expected \"{}\" at position {}",
+                self.opened_at.unwrap_or(0),
+                expected,
+                self.position
+            ),
+            (None, Some(found)) => write!(
+                f,
+                "Unexpected \"{}\" at position {}: nothing to close",
+                found, self.position
+            ),
+            (None, None) => write!(f, "Malformed text at position {}", self.position),
+        }
+    }
+}
+
+fn closer_for(opener: char) -> Option<char> {
+    match opener {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '{' => Some('}'),
+        '<' => Some('>'),
+        _ => None,
+    }
+}
+
+fn is_tag_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_' | ':' | '.')
+}
+
+// Элементы в стеке хранятся как "позиция закрывающий"
+fn pop_expected(stack: &mut Stack) -> Option<(usize, String)> {
+    let entry = stack.pop().ok()?;
+    let (position, closer) = entry.split_once(' ')?;
+    Some((position.parse().ok()?, closer.to_string()))
+}
+
+// Разбор тега, начинающегося с "<" в позиции start.
+// Возвращает (закрывающий ли, имя, самозакрывающийся ли, позиция после ">").
+fn parse_tag(chars: &[char], start: usize) -> Result<(bool, String, bool, usize), BalanceError> {
+    let mut i = start + 1;
+    let closing = chars.get(i) == Some(&'/');
+    if closing {
+        i += 1;
+    }
+
+    let name_start = i;
+    while i < chars.len() && is_tag_name_char(chars[i]) {
+        i += 1;
+    }
+    let name: String = chars[name_start..i].iter().collect();
+
+    while i < chars.len() && chars[i] != '>' {
+        i += 1;
+    }
+    if i == chars.len() || name.is_empty() {
+        return Err(BalanceError {
+            position: i,
+            expected: Some(">".to_string()),
+            found: None,
+            opened_at: Some(start),
+        });
+    }
+
+    let self_closing = !closing && chars[i - 1] == '/';
+    Ok((closing, name, self_closing, i + 1))
+}
+
+pub fn check_balanced(text: &str) -> Result<(), BalanceError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut stack = Stack::new(16);
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let starts_tag = c == '<'
+            && chars
+                .get(i + 1)
+                .is_some_and(|&next| next == '/' || next.is_alphabetic());
+
+        // Открывающие элементы кладут в стек ожидаемый закрывающий,
+        // закрывающие дают (найденный элемент, его позиция)
+        let (found, found_at) = if starts_tag {
+            let (closing, name, self_closing, next) = parse_tag(&chars, i)?;
+            let start = i;
+            i = next;
+            if self_closing {
+                continue;
+            }
+            if !closing {
+                stack.push(format!("{} </{}>", start, name)).expect("unbounded stack");
+                continue;
+            }
+            (format!("</{}>", name), start)
+        } else if let Some(closer) = closer_for(c) {
+            stack.push(format!("{} {}", i, closer)).expect("unbounded stack");
+            i += 1;
+            continue;
+        } else if matches!(c, ')' | ']' | '}' | '>') {
+            i += 1;
+            (c.to_string(), i - 1)
+        } else {
+            i += 1;
+            continue;
+        };
+
+        // Закрывающий элемент должен совпасть с вершиной стека
+        match pop_expected(&mut stack) {
+            Some((_, expected)) if expected == found => {}
+            Some((opened_at, expected)) => {
+                return Err(BalanceError {
+                    position: found_at,
+                    expected: Some(expected),
+                    found: Some(found),
+                    opened_at: Some(opened_at),
+                })
+            }
+            None => {
+                return Err(BalanceError {
+                    position: found_at,
+                    expected: None,
+                    found: Some(found),
+                    opened_at: None,
+                })
+            }
+        }
+    }
+
+    match pop_expected(&mut stack) {
+        Some((opened_at, expected)) => Err(BalanceError {
+            position: chars.len(),
+            expected: Some(expected),
+            found: None,
+            opened_at: Some(opened_at),
+        }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(text: &str) -> String {
+        check_balanced(text).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn accepts_balanced_text() {
+        assert_eq!(check_balanced(""), Ok(()));
+        assert_eq!(check_balanced("f(a[b]{c}) <1>"), Ok(()));
+        assert_eq!(check_balanced("<div><p>text</p><br/></div>"), Ok(()));
+        assert_eq!(check_balanced("a < b > c"), Ok(()));
+    }
+
+    #[test]
+    fn reports_mismatched_closer() {
+        assert_eq!(
+            check_balanced("(]"),
+            Err(BalanceError {
+                position: 1,
+                expected: Some(")".to_string()),
+                found: Some("]".to_string()),
+                opened_at: Some(0),
+            })
+        );
+        assert_eq!(
+            error("<div></span>"),
+            "Mismatch at position 5: expected \"</div>\" (opened at position 0) but found \"</span>\""
+        );
+    }
+
+    #[test]
+    fn reports_unexpected_closer() {
+        assert_eq!(error("x)"), "Unexpected \")\" at position 1: nothing to close");
+        assert_eq!(error("</p>"), "Unexpected \"</p>\" at position 0: nothing to close");
+        // Позиции считаются в символах, а не в байтах
+        assert_eq!(error("ü]"), "Unexpected \"]\" at position 1: nothing to close");
+    }
+
+    #[test]
+    fn reports_unclosed_elements() {
+        assert_eq!(error("(("), "Unclosed element opened at position 1: expected \")\" at position 2");
+        assert_eq!(error("a < b"), "Unclosed element opened at position 2: expected \">\" at position 5");
+        assert_eq!(error("<p>"), "Unclosed element opened at position 0: expected \"</p>\" at position 3");
+    }
+
+    #[test]
+    fn reports_malformed_tags() {
+        assert_eq!(error("<div"), "Unclosed element opened at position 0: expected \">\" at position 4");
+        assert_eq!(error("</>"), "Unclosed element opened at position 0: expected \">\" at position 2");
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+// Ошибки различаются, чтобы потребитель мог продолжить ожидание после таймаута
+// и завершиться после закрытия очереди
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum QueueError {
+    // Очередь закрыта (и пуста — для извлечения)
+    Closed,
+    // Элемент не появился за отведённое время
+    Timeout,
+}
+
+impl fmt::Display for QueueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueueError::Closed => write!(f, "Queue is closed"),
+            QueueError::Timeout => write!(f, "Timed out waiting for an element"),
+        }
+    }
+}
+
+// Ограниченная блокирующая очередь для нескольких производителей и потребителей.
+// Для совместного использования потоками оборачивается в Arc.
+// После close() новые элементы не принимаются, оставшиеся можно извлечь.
+pub struct BlockingQueue {
+    state: Mutex<State>,
+    // Сигнал потребителям: появился элемент или очередь закрыта
+    not_empty: Condvar,
+    // Сигнал производителям: освободилось место или очередь закрыта
+    not_full: Condvar,
+    capacity: usize,
+}
+
+struct State {
+    items: VecDeque<String>,
+    closed: bool,
+}
+
+impl BlockingQueue {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        BlockingQueue {
+            state: Mutex::new(State {
+                items: VecDeque::with_capacity(capacity),
+                closed: false,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            capacity,
+        }
+    }
+
+    // Паника в другом потоке не должна делать очередь непригодной:
+    // состояние остаётся согласованным, поэтому отравление игнорируется
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().items.is_empty()
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    // Ждёт свободного места; ошибка, если очередь закрыта
+    pub fn push(&self, value: String) -> Result<(), QueueError> {
+        let mut state = self.lock();
+        while state.items.len() == self.capacity && !state.closed {
+            state = self.not_full.wait(state).unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        if state.closed {
+            return Err(QueueError::Closed);
+        }
+        state.items.push_back(value);
+        drop(state);
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    // Ждёт элемента; ошибка, если очередь закрыта и пуста
+    pub fn pop(&self) -> Result<String, QueueError> {
+        let mut state = self.lock();
+        while state.items.is_empty() && !state.closed {
+            state = self.not_empty.wait(state).unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        self.take(state)
+    }
+
+    // Ждёт элемента не дольше timeout; слишком большой timeout означает ожидание без срока
+    pub fn try_pop(&self, timeout: Duration) -> Result<String, QueueError> {
+        let Some(deadline) = Instant::now().checked_add(timeout) else {
+            return self.pop();
+        };
+        let mut state = self.lock();
+        while state.items.is_empty() && !state.closed {
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(QueueError::Timeout);
+            }
+            state = self
+                .not_empty
+                .wait_timeout(state, deadline - now)
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .0;
+        }
+        self.take(state)
+    }
+
+    // Будит все ожидающие потоки
+    pub fn close(&self) {
+        self.lock().closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    fn take(&self, mut state: MutexGuard<'_, State>) -> Result<String, QueueError> {
+        match state.items.pop_front() {
+            Some(value) => {
+                drop(state);
+                self.not_full.notify_one();
+                Ok(value)
+            }
+            None => Err(QueueError::Closed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BlockingQueue, QueueError};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn every_element_is_received_exactly_once() {
+        let (producers, consumers, items) = (4, 3, 500);
+        let queue = Arc::new(BlockingQueue::new(8));
+
+        let consumer_handles: Vec<_> = (0..consumers)
+            .map(|consumer| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    let mut received = Vec::new();
+                    // Часть потребителей ждёт через pop, часть — через try_pop с таймаутом
+                    loop {
+                        let result = if consumer % 2 == 0 {
+                            queue.pop()
+                        } else {
+                            queue.try_pop(Duration::from_millis(5))
+                        };
+                        match result {
+                            Ok(value) => received.push(value),
+                            Err(QueueError::Timeout) => {}
+                            Err(QueueError::Closed) => break,
+                        }
+                    }
+                    received
+                })
+            })
+            .collect();
+
+        let producer_handles: Vec<_> = (0..producers)
+            .map(|producer| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    for item in 0..items {
+                        queue.push(format!("{}-{}", producer, item)).unwrap();
+                    }
+                })
+            })
+            .collect();
+
+        for handle in producer_handles {
+            handle.join().unwrap();
+        }
+        queue.close();
+
+        let mut received: Vec<String> = consumer_handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect();
+        received.sort();
+        let mut expected: Vec<String> = (0..producers)
+            .flat_map(|producer| (0..items).map(move |item| format!("{}-{}", producer, item)))
+            .collect();
+        expected.sort();
+        assert_eq!(received, expected);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn close_wakes_blocked_consumers_and_producers() {
+        let queue = Arc::new(BlockingQueue::new(1));
+        let consumers: Vec<_> = (0..3)
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || queue.pop())
+            })
+            .collect();
+        thread::sleep(Duration::from_millis(50));
+        queue.close();
+        for handle in consumers {
+            assert_eq!(handle.join().unwrap(), Err(QueueError::Closed));
+        }
+
+        let queue = Arc::new(BlockingQueue::new(1));
+        queue.push("a".to_string()).unwrap();
+        let producer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || queue.push("b".to_string()))
+        };
+        thread::sleep(Duration::from_millis(50));
+        queue.close();
+        assert_eq!(producer.join().unwrap(), Err(QueueError::Closed));
+        // Оставшиеся элементы можно забрать и после закрытия
+        assert_eq!(queue.pop(), Ok("a".to_string()));
+        assert_eq!(queue.pop(), Err(QueueError::Closed));
+    }
+
+    #[test]
+    fn try_pop_times_out_on_empty_queue() {
+        let queue = BlockingQueue::new(2);
+        let started = Instant::now();
+        assert_eq!(queue.try_pop(Duration::from_millis(30)), Err(QueueError::Timeout));
+        assert!(started.elapsed() >= Duration::from_millis(30));
+
+        queue.push("a".to_string()).unwrap();
+        assert_eq!(queue.try_pop(Duration::ZERO), Ok("a".to_string()));
+        queue.close();
+        assert_eq!(queue.try_pop(Duration::from_secs(10)), Err(QueueError::Closed));
+    }
+
+    #[test]
+    fn capacity_is_at_least_one() {
+        let queue = BlockingQueue::new(0);
+        assert_eq!(queue.capacity(), 1);
+        queue.push("a".to_string()).unwrap();
+        assert_eq!(queue.len(), 1);
+        assert!(!queue.is_closed());
+    }
+
+    #[test]
+    fn huge_timeout_waits_without_deadline() {
+        let queue = Arc::new(BlockingQueue::new(1));
+        let producer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(30));
+                queue.push("late".to_string())
+            })
+        };
+        assert_eq!(queue.try_pop(Duration::MAX), Ok("late".to_string()));
+        producer.join().unwrap().unwrap();
+        queue.close();
+        assert_eq!(queue.try_pop(Duration::MAX), Err(QueueError::Closed));
+    }
+
+    #[test]
+    fn errors_have_messages() {
+        assert_eq!(QueueError::Closed.to_string(), "Queue is closed");
+        assert_eq!(QueueError::Timeout.to_string(), "Timed out waiting for an element");
+    }
+}
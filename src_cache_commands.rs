@@ -0,0 +1,223 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::lru_cache::LruCache;
+use crate::output::{CommandResult, ErrorKind};
+use crate::registry::{restore_command, Call, CommandSpec, DataFile, DataStructures, Module};
+
+// Команды LRU-кэша
+pub struct CacheModule {
+    cache: LruCache,
+}
+
+impl CacheModule {
+    // О вытеснении элементов кэш сообщает через notices
+    pub fn new(capacity: usize, notices: Rc<RefCell<Vec<String>>>) -> Self {
+        let mut cache = LruCache::new(capacity);
+        cache.set_on_evict(move |key, value| {
+            notices
+                .borrow_mut()
+                .push(format!("Evicted \"{}\" => \"{}\" from cache", key, value))
+        });
+        CacheModule { cache }
+    }
+
+    // Ёмкость, затем пары от самой старой к самой свежей
+    fn values(&self) -> Vec<String> {
+        let mut values = vec![self.cache.capacity().to_string()];
+        for (key, value) in self.cache.iter().rev() {
+            values.push(key.clone());
+            values.push(value.clone());
+        }
+        values
+    }
+
+    fn restore_command(&self) -> String {
+        restore_command("lru_cache", self.values())
+    }
+}
+
+const COMMANDS: &[CommandSpec] = &[
+    CommandSpec { name: "CPUT", signature: "<key> <value>", help: "Add or update a value", lookup: false },
+    CommandSpec { name: "CGET", signature: "<key>", help: "Get a value and mark the key as recently used", lookup: true },
+    CommandSpec { name: "CPEEK", signature: "<key>", help: "Get a value without changing the order", lookup: true },
+    CommandSpec { name: "CDEL", signature: "<key>", help: "Remove a key", lookup: false },
+    CommandSpec { name: "CCAPACITY", signature: "[capacity:usize]", help: "Show or set the capacity", lookup: false },
+    CommandSpec { name: "CLEN", signature: "", help: "Show the number of entries", lookup: true },
+    CommandSpec { name: "CPRINT", signature: "", help: "Print the entries from most to least recent", lookup: true },
+];
+
+const FILES: &[DataFile] = &[DataFile {
+    suffix: "lru_cache",
+    description: Some("LRU cache"),
+}];
+
+impl Module for CacheModule {
+    fn title(&self) -> &'static str {
+        "LRU Cache"
+    }
+
+    fn name(&self) -> &'static str {
+        "lru_cache"
+    }
+
+    fn commands(&self) -> &'static [CommandSpec] {
+        COMMANDS
+    }
+
+    fn execute(&mut self, call: &Call, ds: &mut DataStructures, out: &mut CommandResult) {
+        let (query, parts) = (call.query, call.parts);
+        match call.command {
+            "CPUT" => {
+                let inverse = self.restore_command();
+                ds.record(query, vec![inverse]);
+                match self.cache.put(parts[1].to_string(), parts[2].to_string()) {
+                    Some(old) => out.line(format!("Updated \"{}\" in cache (was \"{}\")", parts[1], old)),
+                    None => out.line(format!("Put \"{}\" => \"{}\" into cache", parts[1], parts[2])),
+                }
+            }
+            "CGET" => {
+                match self.cache.get(parts[1]) {
+                    Some(value) => {
+                        out.line(format!("Cache[\"{}\"] = \"{}\"", parts[1], value));
+                        out.set_value(value);
+                    }
+                    None => {
+                        out.line(format!("Key \"{}\" not found in cache", parts[1]));
+                        out.set_error(ErrorKind::NotFound, "Key not found");
+                    }
+                }
+            }
+            "CPEEK" => {
+                match self.cache.peek(parts[1]) {
+                    Some(value) => {
+                        out.line(format!("Cache[\"{}\"] = \"{}\"", parts[1], value));
+                        out.set_value(value);
+                    }
+                    None => {
+                        out.line(format!("Key \"{}\" not found in cache", parts[1]));
+                        out.set_error(ErrorKind::NotFound, "Key not found");
+                    }
+                }
+            }
+            "CDEL" => {
+                let inverse = self.restore_command();
+                match self.cache.remove(parts[1]) {
+                    Some(_) => {
+                        out.line(format!("Removed \"{}\" from cache", parts[1]));
+                        ds.record(query, vec![inverse]);
+                    }
+                    None => {
+                        out.line(format!("Key \"{}\" not found in cache", parts[1]));
+                        out.set_error(ErrorKind::NotFound, "Key not found");
+                    }
+                }
+            }
+            "CCAPACITY" => {
+                if parts.len() > 1 {
+                    let capacity = call.number::<usize>(1);
+                    let inverse = self.restore_command();
+                    self.cache.set_capacity(capacity);
+                    out.line(format!("Cache capacity set to {}", capacity));
+                    ds.record(query, vec![inverse]);
+                } else {
+                    out.line(format!("Cache capacity: {}", self.cache.capacity()));
+                    out.set_value(self.cache.capacity());
+                }
+            }
+            "CLEN" => {
+                out.line(format!("Cache size: {}", self.cache.len()));
+                out.set_value(self.cache.len());
+            }
+            "CPRINT" => {
+                let values: Vec<String> = self.cache.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+                out.line(format!("LRU Cache (most recent first): {}", values.iter().map(|value| format!("{} ", value)).collect::<String>()));
+                out.set_values(values);
+            }
+            _ => unreachable!("{} is not a cache command", call.command),
+        }
+    }
+
+    fn files(&self) -> &'static [DataFile] {
+        FILES
+    }
+
+    fn load(&mut self, _file: &str, lines: &[String]) -> Vec<String> {
+        let mut tokens = lines.iter().flat_map(|line| line.split_whitespace());
+        if let Some(capacity) = tokens.next().and_then(|token| token.parse::<usize>().ok()) {
+            self.cache.set_capacity(capacity);
+        }
+        while let (Some(key), Some(value)) = (tokens.next(), tokens.next()) {
+            self.cache.put(key.to_string(), value.to_string());
+        }
+        Vec::new()
+    }
+
+    fn save(&self, _file: &str) -> String {
+        let mut values = self.values().into_iter();
+        let mut text = format!("{}\n", values.next().unwrap_or_default());
+        for value in values {
+            text.push_str(&value);
+            text.push(' ');
+        }
+        text
+    }
+
+    fn snapshot(&self) -> Option<String> {
+        Some(self.restore_command())
+    }
+
+    fn restore(&mut self, _target: &str, values: &[&str]) -> Result<(), String> {
+        let mut values = values.iter();
+        let capacity = values.next().and_then(|capacity| capacity.parse::<usize>().ok());
+        self.cache.clear();
+        self.cache.set_capacity(capacity.ok_or_else(|| "Invalid cache snapshot".to_string())?);
+        while let (Some(key), Some(value)) = (values.next(), values.next()) {
+            self.cache.put(key.to_string(), value.to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::CacheModule;
+    use crate::output::ErrorKind;
+    use crate::registry::Module;
+    use crate::testing::{printed, run, structures};
+
+    #[test]
+    fn put_reports_eviction_and_undo_restores_it() {
+        let mut ds = structures();
+        run(&mut ds, &["CCAPACITY 2", "CPUT a 1", "CPUT b 2", "CGET a"]);
+        let out = run(&mut ds, &["CPUT c 3"]);
+        assert_eq!(out.lines, ["Put \"c\" => \"3\" into cache", "Evicted \"b\" => \"2\" from cache"]);
+        assert_eq!(printed(&mut ds, "CPRINT"), "LRU Cache (most recent first): c=3 a=1 ");
+        run(&mut ds, &["UNDO"]);
+        assert_eq!(printed(&mut ds, "CPRINT"), "LRU Cache (most recent first): a=1 b=2 ");
+    }
+
+    #[test]
+    fn missing_keys_are_not_found() {
+        let mut ds = structures();
+        assert_eq!(run(&mut ds, &["CGET x"]).kind, Some(ErrorKind::NotFound));
+        assert_eq!(run(&mut ds, &["CPEEK x"]).kind, Some(ErrorKind::NotFound));
+        assert_eq!(run(&mut ds, &["CDEL x"]).kind, Some(ErrorKind::NotFound));
+    }
+
+    #[test]
+    fn save_and_load_preserve_recency_order() {
+        let mut ds = structures();
+        run(&mut ds, &["CCAPACITY 3", "CPUT a 1", "CPUT b 2", "CPUT c 3", "CGET a"]);
+        let saved = ds.get_mut::<CacheModule>().unwrap().save("lru_cache");
+        assert_eq!(saved, "3\nb 2 c 3 a 1 ");
+
+        let mut loaded = CacheModule::new(10, Rc::new(RefCell::new(Vec::new())));
+        let lines: Vec<String> = saved.lines().map(|line| line.to_string()).collect();
+        assert!(loaded.load("lru_cache", &lines).is_empty());
+        assert_eq!(loaded.save("lru_cache"), saved);
+    }
+}
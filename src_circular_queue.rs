@@ -0,0 +1,193 @@
+// Кольцевая очередь фиксированной ёмкости
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OverflowPolicy {
+    // Новый элемент вытесняет самый старый
+    Overwrite,
+    // Новый элемент отклоняется с ошибкой
+    Reject,
+}
+
+impl OverflowPolicy {
+    pub fn name(&self) -> &'static str {
+        match self {
+            OverflowPolicy::Overwrite => "overwrite",
+            OverflowPolicy::Reject => "reject",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "overwrite" => Some(OverflowPolicy::Overwrite),
+            "reject" => Some(OverflowPolicy::Reject),
+            _ => None,
+        }
+    }
+}
+
+pub struct CircularQueue {
+    buffer: Vec<Option<String>>,
+    head: usize,
+    len: usize,
+    policy: OverflowPolicy,
+}
+
+impl CircularQueue {
+    pub fn new(capacity: usize, policy: OverflowPolicy) -> Self {
+        Self::with_head(capacity, policy, 0)
+    }
+
+    // Пустая очередь, первый элемент которой будет записан в позицию head
+    pub fn with_head(capacity: usize, policy: OverflowPolicy, head: usize) -> Self {
+        let capacity = capacity.max(1);
+        CircularQueue {
+            buffer: (0..capacity).map(|_| None).collect(),
+            head: head % capacity,
+            len: 0,
+            policy,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    pub fn head(&self) -> usize {
+        self.head
+    }
+
+    // Позиция, в которую будет записан следующий элемент
+    pub fn tail(&self) -> usize {
+        (self.head + self.len) % self.buffer.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.buffer.len()
+    }
+
+    // При переполнении в режиме Overwrite возвращает вытесненный элемент
+    pub fn enqueue(&mut self, value: String) -> Result<Option<String>, String> {
+        let tail = self.tail();
+        if !self.is_full() {
+            self.buffer[tail] = Some(value);
+            self.len += 1;
+            return Ok(None);
+        }
+
+        match self.policy {
+            OverflowPolicy::Reject => Err(format!("Queue is full (capacity {})", self.buffer.len())),
+            OverflowPolicy::Overwrite => {
+                let overwritten = self.buffer[tail].replace(value);
+                self.head = (self.head + 1) % self.buffer.len();
+                Ok(overwritten)
+            }
+        }
+    }
+
+    pub fn dequeue(&mut self) -> Result<String, String> {
+        if self.len == 0 {
+            return Err("Queue is empty".to_string());
+        }
+        let value = self.buffer[self.head].take().unwrap();
+        self.head = (self.head + 1) % self.buffer.len();
+        self.len -= 1;
+        Ok(value)
+    }
+
+    pub fn peek(&self) -> Result<&String, String> {
+        if self.len == 0 {
+            return Err("Queue is empty".to_string());
+        }
+        Ok(self.buffer[self.head].as_ref().unwrap())
+    }
+
+    // От первого элемента к последнему
+    pub fn iter(&self) -> impl Iterator<Item = &String> {
+        (0..self.len).map(move |i| {
+            self.buffer[(self.head + i) % self.buffer.len()]
+                .as_ref()
+                .unwrap()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CircularQueue, OverflowPolicy};
+
+    fn values(queue: &CircularQueue) -> Vec<&str> {
+        queue.iter().map(String::as_str).collect()
+    }
+
+    fn filled(capacity: usize, policy: OverflowPolicy, items: &[&str]) -> CircularQueue {
+        let mut queue = CircularQueue::new(capacity, policy);
+        for item in items {
+            queue.enqueue(item.to_string()).unwrap();
+        }
+        queue
+    }
+
+    #[test]
+    fn wraps_around_the_buffer() {
+        let mut queue = filled(3, OverflowPolicy::Reject, &["a", "b", "c"]);
+        assert!(queue.is_full());
+        assert_eq!(queue.dequeue().unwrap(), "a");
+        assert_eq!(queue.dequeue().unwrap(), "b");
+        queue.enqueue("d".to_string()).unwrap();
+        queue.enqueue("e".to_string()).unwrap();
+        assert_eq!((queue.head(), queue.tail()), (2, 2));
+        assert_eq!(values(&queue), ["c", "d", "e"]);
+        assert_eq!(queue.peek().unwrap(), "c");
+    }
+
+    #[test]
+    fn reject_keeps_contents_when_full() {
+        let mut queue = filled(2, OverflowPolicy::Reject, &["a", "b"]);
+        assert_eq!(queue.enqueue("c".to_string()), Err("Queue is full (capacity 2)".to_string()));
+        assert_eq!(values(&queue), ["a", "b"]);
+    }
+
+    #[test]
+    fn overwrite_replaces_oldest() {
+        let mut queue = filled(2, OverflowPolicy::Overwrite, &["a", "b"]);
+        assert_eq!(queue.enqueue("c".to_string()), Ok(Some("a".to_string())));
+        assert_eq!(queue.enqueue("d".to_string()), Ok(Some("b".to_string())));
+        assert_eq!(queue.len(), 2);
+        assert_eq!(values(&queue), ["c", "d"]);
+    }
+
+    #[test]
+    fn empty_queue_and_minimal_capacity() {
+        let mut queue = CircularQueue::with_head(0, OverflowPolicy::Overwrite, 5);
+        assert_eq!((queue.capacity(), queue.head()), (1, 0));
+        assert_eq!(queue.dequeue(), Err("Queue is empty".to_string()));
+        assert!(queue.peek().is_err());
+        queue.enqueue("a".to_string()).unwrap();
+        assert_eq!(queue.enqueue("b".to_string()), Ok(Some("a".to_string())));
+        assert_eq!(queue.dequeue().unwrap(), "b");
+        assert!(queue.is_empty());
+
+        let queue = CircularQueue::with_head(4, OverflowPolicy::Reject, 6);
+        assert_eq!((queue.head(), queue.tail()), (2, 2));
+    }
+
+    #[test]
+    fn policy_names_round_trip() {
+        for policy in [OverflowPolicy::Overwrite, OverflowPolicy::Reject] {
+            assert_eq!(OverflowPolicy::parse(policy.name()), Some(policy));
+        }
+        assert_eq!(OverflowPolicy::parse("REJECT"), Some(OverflowPolicy::Reject));
+        assert_eq!(OverflowPolicy::parse("drop"), None);
+    }
+}
@@ -0,0 +1,246 @@
+use std::path::PathBuf;
+
+use crate::queue_commands::QueueBackend;
+
+// Разбор аргументов командной строки
+
+// Откуда берутся команды
+pub enum Mode {
+    // Одна команда: --query или подкоманда query
+    Query(String),
+    // Команды из файла, по одной в строке: --script или подкоманда script
+    Script(String),
+    // Интерактивный режим: подкоманда repl
+    Repl,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    // Один JSON-объект на команду, по одному в строке
+    Json,
+}
+
+impl OutputFormat {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "text" => Some(OutputFormat::Text),
+            "json" => Some(OutputFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+pub struct Options {
+    pub file: String,
+    pub data_dir: Option<String>,
+    pub mode: Mode,
+    pub format: OutputFormat,
+    pub queue_backend: QueueBackend,
+    // Выполнить команды, не сохраняя изменения
+    pub dry_run: bool,
+    // Не выводить сообщения о загрузке и сохранении файлов
+    pub quiet: bool,
+}
+
+impl Options {
+    // Базовое имя файлов данных с учётом --data-dir
+    pub fn base_filename(&self) -> String {
+        match &self.data_dir {
+            Some(dir) => PathBuf::from(dir).join(&self.file).to_string_lossy().into_owned(),
+            None => self.file.clone(),
+        }
+    }
+}
+
+pub enum Action {
+    Run(Options),
+    Help,
+    Version,
+}
+
+pub fn usage(program: &str) -> String {
+    format!(
+        "Usage: {program} [OPTIONS] --query <command>
+       {program} [OPTIONS] query <command>
+       {program} [OPTIONS] script <path>
+       {program} [OPTIONS] repl
+
+Options:
+  --file <name>             Base name of the data files (default: data)
+  --data-dir <dir>          Directory for the data files (created if missing)
+  --query <command>         Run a single command
+  --script <path>           Run commands from a file, one per line (# starts a comment)
+  --output <text|json>      Output format (default: text); --format is an alias
+  --queue-backend <name>    Queue implementation: linked (default) or two-stacks
+  --dry-run                 Run commands without saving changes
+  --quiet                   Do not report loaded and saved files
+  -h, --help                Show this help
+  -V, --version             Show the version
+
+Example: {program} --file data --query 'MPUSH_BACK hello'"
+    )
+}
+
+fn set_mode(mode: &mut Option<Mode>, new_mode: Mode) -> Result<(), String> {
+    if mode.is_some() {
+        return Err("Only one of --query, --script, query, script or repl may be given".to_string());
+    }
+    *mode = Some(new_mode);
+    Ok(())
+}
+
+// Флаги принимаются в любом порядке; значения передаются следующим аргументом
+// или через "=": --file data или --file=data
+pub fn parse_args(args: &[String]) -> Result<Action, String> {
+    let mut file = None;
+    let mut data_dir = None;
+    let mut mode = None;
+    let mut format = OutputFormat::Text;
+    let mut queue_backend = QueueBackend::Linked;
+    let mut dry_run = false;
+    let mut quiet = false;
+
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = |name: &str| -> Result<String, String> {
+            match inline_value.clone().or_else(|| args.next().cloned()) {
+                Some(value) => Ok(value),
+                None => Err(format!("{} requires a value", name)),
+            }
+        };
+
+        match flag {
+            "-h" | "--help" => return Ok(Action::Help),
+            "-V" | "--version" => return Ok(Action::Version),
+            "--file" => file = Some(value("--file <name>")?),
+            "--data-dir" => data_dir = Some(value("--data-dir <dir>")?),
+            "--query" | "query" => set_mode(&mut mode, Mode::Query(value("--query <command>")?))?,
+            "--script" | "script" => set_mode(&mut mode, Mode::Script(value("--script <path>")?))?,
+            "repl" => set_mode(&mut mode, Mode::Repl)?,
+            "--output" | "--format" => {
+                let name = value("--output <text|json>")?;
+                format = OutputFormat::parse(&name)
+                    .ok_or_else(|| format!("Unknown output format \"{}\" (expected text or json)", name))?;
+            }
+            "--queue-backend" => {
+                let name = value("--queue-backend <name>")?;
+                queue_backend = QueueBackend::parse(&name)
+                    .ok_or_else(|| format!("Unknown queue backend \"{}\" (expected linked or two-stacks)", name))?;
+            }
+            "--dry-run" | "--quiet" if inline_value.is_some() => {
+                return Err(format!("{} does not take a value", flag))
+            }
+            "--dry-run" => dry_run = true,
+            "--quiet" => quiet = true,
+            _ if flag.starts_with('-') => return Err(format!("Unknown option \"{}\"", flag)),
+            _ => return Err(format!("Unexpected argument \"{}\"", arg)),
+        }
+    }
+
+    let mode = mode.ok_or_else(|| "No command given: use --query, --script or repl".to_string())?;
+    if let Mode::Query(query) = &mode {
+        if query.trim().is_empty() {
+            return Err("--query requires a non-empty command".to_string());
+        }
+    }
+
+    Ok(Action::Run(Options {
+        file: file.unwrap_or_else(|| "data".to_string()),
+        data_dir,
+        mode,
+        format,
+        queue_backend,
+        dry_run,
+        // Сообщения о файлах не должны смешиваться с JSON
+        quiet: quiet || format == OutputFormat::Json,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_args, usage, Action, Mode, Options, OutputFormat};
+    use crate::queue_commands::QueueBackend;
+
+    fn parse(args: &[&str]) -> Result<Action, String> {
+        let args: Vec<String> = std::iter::once("lab").chain(args.iter().copied()).map(String::from).collect();
+        parse_args(&args)
+    }
+
+    fn options(args: &[&str]) -> Options {
+        match parse(args) {
+            Ok(Action::Run(options)) => options,
+            Ok(_) => panic!("expected options for {:?}", args),
+            Err(e) => panic!("{:?}: {}", args, e),
+        }
+    }
+
+    fn error(args: &[&str]) -> String {
+        match parse(args) {
+            Err(e) => e,
+            Ok(_) => panic!("expected an error for {:?}", args),
+        }
+    }
+
+    #[test]
+    fn defaults_and_query_forms() {
+        let parsed = options(&["--query", "SPUSH a"]);
+        assert!(matches!(&parsed.mode, Mode::Query(query) if query == "SPUSH a"));
+        assert_eq!(parsed.file, "data");
+        assert_eq!(parsed.base_filename(), "data");
+        assert!(parsed.format == OutputFormat::Text);
+        assert!(matches!(parsed.queue_backend, QueueBackend::Linked));
+        assert!(!parsed.dry_run && !parsed.quiet);
+
+        assert!(matches!(options(&["query", "SLEN"]).mode, Mode::Query(_)));
+        assert!(matches!(options(&["script", "cmds.txt"]).mode, Mode::Script(path) if path == "cmds.txt"));
+        assert!(matches!(options(&["repl"]).mode, Mode::Repl));
+    }
+
+    #[test]
+    fn flags_in_any_order_and_inline_values() {
+        let parsed = options(&["--dry-run", "repl", "--file=store", "--data-dir", "dir", "--queue-backend=two-stacks", "--quiet"]);
+        assert_eq!(parsed.base_filename(), std::path::Path::new("dir").join("store").to_string_lossy());
+        assert!(matches!(parsed.queue_backend, QueueBackend::TwoStacks));
+        assert!(parsed.dry_run && parsed.quiet);
+
+        // JSON не смешивается с сообщениями о файлах
+        let parsed = options(&["--format", "JSON", "query", "SLEN"]);
+        assert!(parsed.format == OutputFormat::Json);
+        assert!(parsed.quiet);
+    }
+
+    #[test]
+    fn help_and_version_win() {
+        assert!(matches!(parse(&["repl", "--help"]), Ok(Action::Help)));
+        assert!(matches!(parse(&["-h"]), Ok(Action::Help)));
+        assert!(matches!(parse(&["-V", "--bogus"]), Ok(Action::Version)));
+        let text = usage("lab");
+        assert!(text.starts_with("Usage: lab [OPTIONS] --query <command>"));
+        assert!(text.contains("--queue-backend <name>"));
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        assert_eq!(error(&[]), "No command given: use --query, --script or repl");
+        assert_eq!(error(&["--query", "  "]), "--query requires a non-empty command");
+        assert_eq!(error(&["--query"]), "--query <command> requires a value");
+        assert_eq!(error(&["repl", "--file"]), "--file <name> requires a value");
+        assert_eq!(
+            error(&["repl", "query", "SLEN"]),
+            "Only one of --query, --script, query, script or repl may be given"
+        );
+        assert_eq!(error(&["repl", "--output", "xml"]), "Unknown output format \"xml\" (expected text or json)");
+        assert_eq!(
+            error(&["repl", "--queue-backend", "array"]),
+            "Unknown queue backend \"array\" (expected linked or two-stacks)"
+        );
+        assert_eq!(error(&["repl", "--quiet=yes"]), "--quiet does not take a value");
+        assert_eq!(error(&["repl", "--verbose"]), "Unknown option \"--verbose\"");
+        assert_eq!(error(&["repl", "extra"]), "Unexpected argument \"extra\"");
+    }
+}
@@ -0,0 +1,256 @@
+use std::mem;
+
+// Дек на кольцевом буфере; свободные ячейки хранят пустые строки
+pub struct Deque {
+    buffer: Vec<String>,
+    head: usize,
+    len: usize,
+}
+
+impl Deque {
+    pub fn new(capacity: usize) -> Self {
+        Deque {
+            buffer: vec![String::new(); capacity],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    // Физический индекс в буфере для логического индекса
+    fn physical(&self, index: usize) -> usize {
+        (self.head + index) % self.buffer.len()
+    }
+
+    fn grow(&mut self) {
+        let new_capacity = (self.buffer.len() * 2).max(4);
+        self.make_contiguous();
+        self.buffer.resize(new_capacity, String::new());
+    }
+
+    pub fn push_back(&mut self, value: String) {
+        if self.len == self.buffer.len() {
+            self.grow();
+        }
+        let index = self.physical(self.len);
+        self.buffer[index] = value;
+        self.len += 1;
+    }
+
+    pub fn push_front(&mut self, value: String) {
+        if self.len == self.buffer.len() {
+            self.grow();
+        }
+        self.head = (self.head + self.buffer.len() - 1) % self.buffer.len();
+        self.buffer[self.head] = value;
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Result<String, String> {
+        if self.len == 0 {
+            return Err("Deque is empty".to_string());
+        }
+        let value = mem::take(&mut self.buffer[self.head]);
+        self.head = (self.head + 1) % self.buffer.len();
+        self.len -= 1;
+        Ok(value)
+    }
+
+    pub fn pop_back(&mut self) -> Result<String, String> {
+        if self.len == 0 {
+            return Err("Deque is empty".to_string());
+        }
+        let index = self.physical(self.len - 1);
+        self.len -= 1;
+        Ok(mem::take(&mut self.buffer[index]))
+    }
+
+    pub fn front(&self) -> Result<&String, String> {
+        self.get(0).map_err(|_| "Deque is empty".to_string())
+    }
+
+    pub fn back(&self) -> Result<&String, String> {
+        if self.len == 0 {
+            return Err("Deque is empty".to_string());
+        }
+        self.get(self.len - 1)
+    }
+
+    pub fn get(&self, index: usize) -> Result<&String, String> {
+        if index >= self.len {
+            return Err("Index out of range".to_string());
+        }
+        Ok(&self.buffer[self.physical(index)])
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Result<&mut String, String> {
+        if index >= self.len {
+            return Err("Index out of range".to_string());
+        }
+        let index = self.physical(index);
+        Ok(&mut self.buffer[index])
+    }
+
+    // Переставляет элементы так, чтобы они лежали в начале буфера подряд
+    pub fn make_contiguous(&mut self) -> &mut [String] {
+        self.buffer.rotate_left(self.head);
+        self.head = 0;
+        &mut self.buffer[..self.len]
+    }
+
+    // Первые n элементов уходят в конец
+    pub fn rotate_left(&mut self, n: usize) {
+        if self.len == 0 {
+            return;
+        }
+        let n = n % self.len;
+        if n > self.len / 2 {
+            return self.rotate_right(self.len - n);
+        }
+        for _ in 0..n {
+            let value = self.pop_front().unwrap();
+            self.push_back(value);
+        }
+    }
+
+    // Последние n элементов уходят в начало
+    pub fn rotate_right(&mut self, n: usize) {
+        if self.len == 0 {
+            return;
+        }
+        let n = n % self.len;
+        if n > self.len / 2 {
+            return self.rotate_left(self.len - n);
+        }
+        for _ in 0..n {
+            let value = self.pop_back().unwrap();
+            self.push_front(value);
+        }
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &String> + ExactSizeIterator {
+        (0..self.len).map(move |i| &self.buffer[self.physical(i)])
+    }
+}
+
+impl FromIterator<String> for Deque {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        let mut deque = Deque::new(0);
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl Extend<String> for Deque {
+    fn extend<I: IntoIterator<Item = String>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(deque: &Deque) -> Vec<&str> {
+        deque.iter().map(String::as_str).collect()
+    }
+
+    // Голова в середине буфера: элементы переходят через его конец
+    fn wrapped() -> Deque {
+        let mut deque = Deque::new(4);
+        deque.push_back("c".to_string());
+        deque.push_back("d".to_string());
+        deque.push_front("b".to_string());
+        deque.push_front("a".to_string());
+        deque
+    }
+
+    #[test]
+    fn pushes_and_pops_at_both_ends() {
+        let mut deque = wrapped();
+        assert_eq!(values(&deque), ["a", "b", "c", "d"]);
+        assert_eq!(deque.capacity(), 4);
+        assert_eq!(deque.pop_front(), Ok("a".to_string()));
+        assert_eq!(deque.pop_back(), Ok("d".to_string()));
+        assert_eq!(deque.front(), Ok(&"b".to_string()));
+        assert_eq!(deque.back(), Ok(&"c".to_string()));
+        assert_eq!(deque.len(), 2);
+        deque.pop_back().unwrap();
+        deque.pop_back().unwrap();
+        assert!(deque.is_empty());
+        assert_eq!(deque.pop_front(), Err("Deque is empty".to_string()));
+        assert_eq!(deque.pop_back(), Err("Deque is empty".to_string()));
+        assert_eq!(deque.front(), Err("Deque is empty".to_string()));
+        assert_eq!(deque.back(), Err("Deque is empty".to_string()));
+    }
+
+    #[test]
+    fn grows_without_reordering_wrapped_elements() {
+        let mut deque = wrapped();
+        deque.push_front("0".to_string());
+        deque.push_back("e".to_string());
+        assert_eq!(values(&deque), ["0", "a", "b", "c", "d", "e"]);
+        assert_eq!(deque.capacity(), 8);
+
+        let mut empty = Deque::new(0);
+        empty.push_front("x".to_string());
+        assert_eq!(values(&empty), ["x"]);
+        assert_eq!(empty.capacity(), 4);
+    }
+
+    #[test]
+    fn indexes_logical_positions() {
+        let mut deque = wrapped();
+        assert_eq!(deque.get(0), Ok(&"a".to_string()));
+        assert_eq!(deque.get(3), Ok(&"d".to_string()));
+        assert_eq!(deque.get(4), Err("Index out of range".to_string()));
+        deque.get_mut(2).unwrap().push('!');
+        assert_eq!(values(&deque), ["a", "b", "c!", "d"]);
+        assert!(deque.get_mut(4).is_err());
+    }
+
+    #[test]
+    fn make_contiguous_keeps_order() {
+        let mut deque = wrapped();
+        let slice = deque.make_contiguous();
+        assert_eq!(slice, ["a", "b", "c", "d"]);
+        slice.sort_by(|a, b| b.cmp(a));
+        assert_eq!(values(&deque), ["d", "c", "b", "a"]);
+        deque.make_contiguous().reverse();
+        deque.push_back("e".to_string());
+        assert_eq!(values(&deque), ["a", "b", "c", "d", "e"]);
+        assert!(Deque::new(2).make_contiguous().is_empty());
+    }
+
+    #[test]
+    fn rotates_in_both_directions() {
+        let mut deque = wrapped();
+        deque.rotate_left(1);
+        assert_eq!(values(&deque), ["b", "c", "d", "a"]);
+        deque.rotate_left(3);
+        assert_eq!(values(&deque), ["a", "b", "c", "d"]);
+        deque.rotate_right(5);
+        assert_eq!(values(&deque), ["d", "a", "b", "c"]);
+        deque.rotate_right(4);
+        assert_eq!(values(&deque), ["d", "a", "b", "c"]);
+        assert_eq!(deque.iter().next_back().map(String::as_str), Some("c"));
+
+        let mut empty = Deque::new(0);
+        empty.rotate_left(3);
+        empty.rotate_right(3);
+        assert!(empty.is_empty());
+    }
+}
@@ -0,0 +1,204 @@
+use crate::deque::Deque;
+use crate::output::{CommandResult, ErrorKind};
+use crate::registry::{restore_command, Call, CommandSpec, DataFile, DataStructures, Module};
+
+// Команды двусторонней очереди
+pub struct DequeModule {
+    deque: Deque,
+}
+
+impl DequeModule {
+    pub fn new() -> Self {
+        DequeModule { deque: Deque::new(10) }
+    }
+
+    fn restore_command(&self) -> String {
+        restore_command("deque", self.deque.iter().cloned())
+    }
+}
+
+const COMMANDS: &[CommandSpec] = &[
+    CommandSpec { name: "DPUSH_FRONT", signature: "<value>", help: "Add a value to the front", lookup: false },
+    CommandSpec { name: "DPUSH_BACK", signature: "<value>", help: "Add a value to the back", lookup: false },
+    CommandSpec { name: "DPOP_FRONT", signature: "", help: "Remove the front value", lookup: false },
+    CommandSpec { name: "DPOP_BACK", signature: "", help: "Remove the back value", lookup: false },
+    CommandSpec { name: "DFRONT", signature: "", help: "Show the front value", lookup: true },
+    CommandSpec { name: "DBACK", signature: "", help: "Show the back value", lookup: true },
+    CommandSpec { name: "DGET", signature: "<index:usize>", help: "Show the element at an index", lookup: true },
+    CommandSpec { name: "DROTATE_LEFT", signature: "<steps:usize>", help: "Rotate the deque left", lookup: false },
+    CommandSpec { name: "DROTATE_RIGHT", signature: "<steps:usize>", help: "Rotate the deque right", lookup: false },
+    CommandSpec { name: "DEMPTY", signature: "", help: "Check whether the deque is empty", lookup: true },
+    CommandSpec { name: "DLEN", signature: "", help: "Show the deque size", lookup: true },
+    CommandSpec { name: "DPRINT", signature: "", help: "Print the deque", lookup: true },
+];
+
+const FILES: &[DataFile] = &[DataFile { suffix: "deque", description: Some("deque") }];
+
+impl Module for DequeModule {
+    fn title(&self) -> &'static str {
+        "Deque"
+    }
+
+    fn name(&self) -> &'static str {
+        "deque"
+    }
+
+    fn commands(&self) -> &'static [CommandSpec] {
+        COMMANDS
+    }
+
+    fn execute(&mut self, call: &Call, ds: &mut DataStructures, out: &mut CommandResult) {
+        let (query, parts) = (call.query, call.parts);
+        match call.command {
+            "DPUSH_FRONT" => {
+                self.deque.push_front(parts[1].to_string());
+                out.line(format!("Pushed \"{}\" to deque front", parts[1]));
+                ds.record(query, vec!["DPOP_FRONT".to_string()]);
+            }
+            "DPUSH_BACK" => {
+                self.deque.push_back(parts[1].to_string());
+                out.line(format!("Pushed \"{}\" to deque back", parts[1]));
+                ds.record(query, vec!["DPOP_BACK".to_string()]);
+            }
+            "DPOP_FRONT" => {
+                match self.deque.pop_front() {
+                    Ok(value) => {
+                        out.line(format!("Popped from deque front: \"{}\"", value));
+                        out.set_value(value.clone());
+                        ds.record(query, vec![format!("DPUSH_FRONT {}", value)]);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Empty, e),
+                }
+            }
+            "DPOP_BACK" => {
+                match self.deque.pop_back() {
+                    Ok(value) => {
+                        out.line(format!("Popped from deque back: \"{}\"", value));
+                        out.set_value(value.clone());
+                        ds.record(query, vec![format!("DPUSH_BACK {}", value)]);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Empty, e),
+                }
+            }
+            "DFRONT" => {
+                match self.deque.front() {
+                    Ok(value) => {
+                        out.line(format!("Deque front: \"{}\"", value));
+                        out.set_value(value);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Empty, e),
+                }
+            }
+            "DBACK" => {
+                match self.deque.back() {
+                    Ok(value) => {
+                        out.line(format!("Deque back: \"{}\"", value));
+                        out.set_value(value);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Empty, e),
+                }
+            }
+            "DGET" => {
+                let index = call.number::<usize>(1);
+                match self.deque.get(index) {
+                    Ok(value) => {
+                        out.line(format!("Deque[{}] = \"{}\"", index, value));
+                        out.set_value(value);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::NotFound, e),
+                }
+            }
+            "DROTATE_LEFT" => {
+                let n = call.number::<usize>(1);
+                self.deque.rotate_left(n);
+                out.line(format!("Rotated deque left by {}", n));
+                ds.record(query, vec![format!("DROTATE_RIGHT {}", n)]);
+            }
+            "DROTATE_RIGHT" => {
+                let n = call.number::<usize>(1);
+                self.deque.rotate_right(n);
+                out.line(format!("Rotated deque right by {}", n));
+                ds.record(query, vec![format!("DROTATE_LEFT {}", n)]);
+            }
+            "DEMPTY" => {
+                out.line(format!("Deque is {}", if self.deque.is_empty() { "empty" } else { "not empty" }));
+                out.set_value(self.deque.is_empty());
+            }
+            "DLEN" => {
+                out.line(format!("Deque size: {}", self.deque.len()));
+                out.set_value(self.deque.len());
+            }
+            "DPRINT" => {
+                let values: Vec<&String> = self.deque.iter().collect();
+                out.line(format!("Deque: {}", values.iter().map(|value| format!("{} ", value)).collect::<String>()));
+                out.set_values(values);
+            }
+            _ => unreachable!("{} is not a deque command", call.command),
+        }
+    }
+
+    fn files(&self) -> &'static [DataFile] {
+        FILES
+    }
+
+    fn load(&mut self, _file: &str, lines: &[String]) -> Vec<String> {
+        for value in lines.iter().flat_map(|line| line.split_whitespace()) {
+            self.deque.push_back(value.to_string());
+        }
+        Vec::new()
+    }
+
+    fn save(&self, _file: &str) -> String {
+        self.deque.iter().map(|value| format!("{} ", value)).collect()
+    }
+
+    fn snapshot(&self) -> Option<String> {
+        Some(self.restore_command())
+    }
+
+    fn restore(&mut self, _target: &str, values: &[&str]) -> Result<(), String> {
+        self.deque = Deque::new(10);
+        for value in values {
+            self.deque.push_back(value.to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DequeModule;
+    use crate::output::ErrorKind;
+    use crate::registry::Module;
+    use crate::testing::{printed, run, structures};
+
+    #[test]
+    fn commands_work_at_both_ends_and_undo() {
+        let mut ds = structures();
+        run(&mut ds, &["DPUSH_BACK b", "DPUSH_FRONT a", "DPUSH_BACK c", "DROTATE_LEFT 1"]);
+        assert_eq!(printed(&mut ds, "DPRINT"), "Deque: b c a ");
+        run(&mut ds, &["DPOP_FRONT", "DPOP_BACK"]);
+        assert_eq!(printed(&mut ds, "DPRINT"), "Deque: c ");
+        run(&mut ds, &["UNDO", "UNDO", "UNDO"]);
+        assert_eq!(printed(&mut ds, "DPRINT"), "Deque: a b c ");
+        assert_eq!(run(&mut ds, &["DGET 3"]).kind, Some(ErrorKind::NotFound));
+    }
+
+    #[test]
+    fn empty_deque_reports_empty() {
+        let mut ds = structures();
+        assert_eq!(run(&mut ds, &["DPOP_FRONT"]).kind, Some(ErrorKind::Empty));
+        assert_eq!(run(&mut ds, &["DBACK"]).kind, Some(ErrorKind::Empty));
+    }
+
+    #[test]
+    fn save_and_load_keep_order() {
+        let mut ds = structures();
+        run(&mut ds, &["DPUSH_FRONT b", "DPUSH_FRONT a", "DPUSH_BACK c"]);
+        let saved = ds.get_mut::<DequeModule>().unwrap().save("deque");
+        let mut loaded = DequeModule::new();
+        assert!(loaded.load("deque", std::slice::from_ref(&saved)).is_empty());
+        assert_eq!(loaded.save("deque"), "a b c ");
+        assert_eq!(saved, "a b c ");
+    }
+}
@@ -1,8 +1,9 @@
 use std::rc::Rc;
-use std::cell::RefCell;
+use std::cell::{RefCell, RefMut};
 
 // Двусвязный список
-type Link = Option<Rc<RefCell<DNode>>>;
+type NodeRef = Rc<RefCell<DNode>>;
+type Link = Option<NodeRef>;
 
 pub struct DNode {
     data: String,
@@ -13,6 +14,9 @@ pub struct DNode {
 pub struct DoublyList {
     head: Link,
     tail: Link,
+    size: usize,
+    // Метка для дескрипторов узлов; заменяется, когда узлы уходят в другой список
+    owner: Rc<()>,
 }
 
 impl DoublyList {
@@ -20,9 +24,19 @@ impl DoublyList {
         DoublyList {
             head: None,
             tail: None,
+            size: 0,
+            owner: Rc::new(()),
         }
     }
 
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
     pub fn add_head(&mut self, value: String) {
         let new_node = Rc::new(RefCell::new(DNode {
             data: value,
@@ -41,6 +55,7 @@ impl DoublyList {
                 self.tail = Some(new_node);
             }
         }
+        self.size += 1;
     }
 
     pub fn add_tail(&mut self, value: String) {
@@ -61,41 +76,218 @@ impl DoublyList {
                 self.tail = Some(new_node);
             }
         }
+        self.size += 1;
     }
 
-    pub fn remove_head(&mut self) -> Result<(), String> {
-        match self.head.take() {
-            Some(old_head) => {
-                match old_head.borrow_mut().next.take() {
-                    Some(new_head) => {
-                        new_head.borrow_mut().prev = None;
-                        self.head = Some(new_head);
-                    }
-                    None => {
-                        self.tail = None;
-                    }
-                }
-                Ok(())
+    pub fn remove_head(&mut self) -> Result<String, String> {
+        self.pop_head().ok_or_else(|| "List is empty".to_string())
+    }
+
+    pub fn remove_tail(&mut self) -> Result<String, String> {
+        self.pop_tail().ok_or_else(|| "List is empty".to_string())
+    }
+
+    pub fn remove_value(&mut self, value: &str) -> Result<String, String> {
+        if self.head.is_none() {
+            return Err("List is empty".to_string());
+        }
+
+        let mut cursor = self.cursor_front_mut();
+        loop {
+            match cursor.current().map(|current| current.as_str() == value) {
+                Some(true) => return Ok(cursor.remove_current().unwrap()),
+                Some(false) => cursor.move_next(),
+                None => return Err("Value not found".to_string()),
             }
-            None => Err("List is empty".to_string()),
         }
     }
 
-    pub fn remove_tail(&mut self) -> Result<(), String> {
-        match self.tail.take() {
-            Some(old_tail) => {
-                match old_tail.borrow_mut().prev.take() {
-                    Some(new_tail) => {
-                        new_tail.borrow_mut().next = None;
-                        self.tail = Some(new_tail);
-                    }
-                    None => {
-                        self.head = None;
-                    }
-                }
-                Ok(())
+    pub fn remove_all(&mut self, value: &str) -> usize {
+        self.retain(|current| current != value)
+    }
+
+    // Оставляет только элементы, для которых keep вернул true; возвращает число удалённых
+    pub fn retain<F: FnMut(&String) -> bool>(&mut self, mut keep: F) -> usize {
+        self.drain_filter(|current| !keep(current)).len()
+    }
+
+    // Извлекает элементы, подходящие под условие, сохраняя их порядок
+    pub fn drain_filter<F: FnMut(&String) -> bool>(&mut self, mut filter: F) -> Vec<String> {
+        let mut removed = Vec::new();
+        let mut cursor = self.cursor_front_mut();
+        loop {
+            match cursor.current().map(|current| filter(&current)) {
+                Some(true) => removed.extend(cursor.remove_current()),
+                Some(false) => cursor.move_next(),
+                None => return removed,
+            }
+        }
+    }
+
+    // При index == len вставляет в конец
+    pub fn insert(&mut self, index: usize, value: String) -> Result<(), String> {
+        if index > self.size {
+            return Err("Index out of range".to_string());
+        }
+
+        match self.node_at(index) {
+            Some(node) => {
+                let prev = node.borrow().prev.clone();
+                self.link_value_between(prev, Some(node), value);
+            }
+            None => self.add_tail(value),
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, index: usize) -> Result<String, String> {
+        let node = self
+            .node_at(index)
+            .ok_or_else(|| "Index out of range".to_string())?;
+        Ok(self.unlink(&node))
+    }
+
+    // Переносит все элементы other в конец списка за O(1)
+    pub fn append(&mut self, other: &mut DoublyList) {
+        if let Some((head, tail, count)) = other.take_chain() {
+            let last = self.tail.clone();
+            self.link_between(last, None, head, tail, count);
+        }
+    }
+
+    // Отрезает элементы начиная с index в новый список
+    pub fn split_off(&mut self, index: usize) -> Result<DoublyList, String> {
+        if index > self.size {
+            return Err("Index out of range".to_string());
+        }
+
+        let mut rest = DoublyList::new();
+        let node = match self.node_at(index) {
+            Some(node) => node,
+            None => return Ok(rest),
+        };
+
+        self.owner = Rc::new(());
+        match node.borrow_mut().prev.take() {
+            Some(prev) => {
+                prev.borrow_mut().next = None;
+                rest.tail = self.tail.replace(prev);
+                rest.head = Some(Rc::clone(&node));
+            }
+            None => {
+                rest.head = self.head.take();
+                rest.tail = self.tail.take();
             }
-            None => Err("List is empty".to_string()),
+        }
+
+        rest.size = self.size - index;
+        self.size = index;
+        Ok(rest)
+    }
+
+    // Сдвигает элементы на n позиций к началу: первые n уходят в конец
+    pub fn rotate_left(&mut self, n: usize) {
+        if self.size == 0 {
+            return;
+        }
+        let mut rest = self.split_off(n % self.size).unwrap();
+        rest.append(self);
+        *self = rest;
+    }
+
+    // Сдвигает элементы на n позиций к концу: последние n уходят в начало
+    pub fn rotate_right(&mut self, n: usize) {
+        if self.size == 0 {
+            return;
+        }
+        let n = n % self.size;
+        self.rotate_left(self.size - n);
+    }
+
+    pub fn reverse(&mut self) {
+        let mut current = self.head.clone();
+        while let Some(node) = current {
+            let mut node = node.borrow_mut();
+            let next = node.next.take();
+            node.next = node.prev.take();
+            node.prev = next.clone();
+            current = next;
+        }
+        std::mem::swap(&mut self.head, &mut self.tail);
+    }
+
+    pub fn swap(&mut self, i: usize, j: usize) -> Result<(), String> {
+        let first = self
+            .node_at(i)
+            .ok_or_else(|| "Index out of range".to_string())?;
+        let second = self
+            .node_at(j)
+            .ok_or_else(|| "Index out of range".to_string())?;
+        if i != j {
+            std::mem::swap(&mut first.borrow_mut().data, &mut second.borrow_mut().data);
+        }
+        Ok(())
+    }
+
+    // Поиск узла по индексу с ближайшего конца
+    fn node_at(&self, index: usize) -> Link {
+        if index >= self.size {
+            return None;
+        }
+
+        if index < self.size / 2 {
+            let mut current = self.head.clone();
+            for _ in 0..index {
+                current = current.and_then(|node| node.borrow().next.clone());
+            }
+            current
+        } else {
+            let mut current = self.tail.clone();
+            for _ in index + 1..self.size {
+                current = current.and_then(|node| node.borrow().prev.clone());
+            }
+            current
+        }
+    }
+
+    // Добавляет в начало и возвращает дескриптор узла для операций за O(1)
+    pub fn push_head_node(&mut self, value: String) -> NodeHandle {
+        self.add_head(value);
+        NodeHandle {
+            node: Rc::clone(self.head.as_ref().unwrap()),
+            owner: Rc::clone(&self.owner),
+        }
+    }
+
+    pub fn move_node_to_head(&mut self, handle: &NodeHandle) -> Result<(), String> {
+        self.check_handle(handle)?;
+        let node = &handle.node;
+        if self.head.as_ref().is_some_and(|head| Rc::ptr_eq(head, node)) {
+            return Ok(());
+        }
+        self.detach(node);
+        let head = self.head.clone();
+        self.link_between(None, head, Rc::clone(node), Rc::clone(node), 1);
+        Ok(())
+    }
+
+    pub fn remove_node(&mut self, handle: NodeHandle) -> Result<String, String> {
+        self.check_handle(&handle)?;
+        Ok(self.unlink(&handle.node))
+    }
+
+    // Дескриптор годен, если выдан этим списком, узлы с тех пор не переносились
+    // в другой список, а сам узел не удалён: у удалённого узла связи очищены,
+    // и он не может быть ни головой, ни хвостом
+    fn check_handle(&self, handle: &NodeHandle) -> Result<(), String> {
+        let is_end = |end: &Link| end.as_ref().is_some_and(|end| Rc::ptr_eq(end, &handle.node));
+        let node = handle.node.borrow();
+        let linked = (node.prev.is_some() || is_end(&self.head))
+            && (node.next.is_some() || is_end(&self.tail));
+        if Rc::ptr_eq(&handle.owner, &self.owner) && linked {
+            Ok(())
+        } else {
+            Err("Node does not belong to this list".to_string())
         }
     }
 
@@ -110,43 +302,848 @@ impl DoublyList {
         false
     }
 
-    pub fn print_forward(&self) {
-        let mut current = self.head.clone();
-        while let Some(node) = current {
-            print!("{} ", node.borrow().data);
-            current = node.borrow().next.clone();
+    pub fn iter(&self) -> DoublyListIter<'_> {
+        DoublyListIter {
+            head: self.head.as_ref(),
+            tail: self.tail.as_ref(),
+            len: self.size,
         }
-        println!();
     }
 
-    pub fn print_backward(&self) {
-        let mut current = self.tail.clone();
-        while let Some(node) = current {
-            print!("{} ", node.borrow().data);
-            current = node.borrow().prev.clone();
+    pub fn iter_mut(&mut self) -> DoublyListIterMut<'_> {
+        DoublyListIterMut {
+            current: self.head.as_ref(),
         }
-        println!();
     }
 
-    pub fn iter(&self) -> DoublyListIter {
-        DoublyListIter {
+    fn pop_head(&mut self) -> Option<String> {
+        let head = self.head.clone()?;
+        Some(self.unlink(&head))
+    }
+
+    fn pop_tail(&mut self) -> Option<String> {
+        let tail = self.tail.clone()?;
+        Some(self.unlink(&tail))
+    }
+
+    pub fn cursor_front(&self) -> Cursor<'_> {
+        Cursor {
+            list: self,
+            current: self.head.as_ref().map(node_ref),
+            index: 0,
+        }
+    }
+
+    pub fn cursor_back(&self) -> Cursor<'_> {
+        Cursor {
+            list: self,
+            current: self.tail.as_ref().map(node_ref),
+            index: self.size.saturating_sub(1),
+        }
+    }
+
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_> {
+        CursorMut {
             current: self.head.clone(),
+            index: 0,
+            list: self,
+        }
+    }
+
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_> {
+        CursorMut {
+            current: self.tail.clone(),
+            index: self.size.saturating_sub(1),
+            list: self,
+        }
+    }
+
+    // Вставка цепочки head..=tail между prev и next (None означает край списка)
+    fn link_between(
+        &mut self,
+        prev: Link,
+        next: Link,
+        head: Rc<RefCell<DNode>>,
+        tail: Rc<RefCell<DNode>>,
+        count: usize,
+    ) {
+        match &prev {
+            Some(node) => node.borrow_mut().next = Some(Rc::clone(&head)),
+            None => self.head = Some(Rc::clone(&head)),
+        }
+        match &next {
+            Some(node) => node.borrow_mut().prev = Some(Rc::clone(&tail)),
+            None => self.tail = Some(Rc::clone(&tail)),
         }
+        head.borrow_mut().prev = prev;
+        tail.borrow_mut().next = next;
+        self.size += count;
+    }
+
+    fn link_value_between(&mut self, prev: Link, next: Link, value: String) {
+        let new_node = Rc::new(RefCell::new(DNode {
+            data: value,
+            prev: None,
+            next: None,
+        }));
+        self.link_between(prev, next, Rc::clone(&new_node), new_node, 1);
+    }
+
+    // Исключает узел из списка, не трогая его данные
+    fn detach(&mut self, node: &Rc<RefCell<DNode>>) {
+        let mut node = node.borrow_mut();
+        let prev = node.prev.take();
+        let next = node.next.take();
+
+        match &prev {
+            Some(prev_node) => prev_node.borrow_mut().next = next.clone(),
+            None => self.head = next.clone(),
+        }
+        match &next {
+            Some(next_node) => next_node.borrow_mut().prev = prev,
+            None => self.tail = prev,
+        }
+
+        self.size -= 1;
+    }
+
+    // Исключает узел из списка и забирает его данные
+    fn unlink(&mut self, node: &Rc<RefCell<DNode>>) -> String {
+        self.detach(node);
+        std::mem::take(&mut node.borrow_mut().data)
+    }
+
+    fn take_chain(&mut self) -> Option<(NodeRef, NodeRef, usize)> {
+        let head = self.head.take()?;
+        let tail = self.tail.take()?;
+        self.owner = Rc::new(());
+        Some((head, tail, std::mem::replace(&mut self.size, 0)))
     }
 }
 
-pub struct DoublyListIter {
-    current: Link,
+impl Default for DoublyList {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl Iterator for DoublyListIter {
-    type Item = String;
+// Дескриптор узла списка. Операции с ним возвращают ошибку, если узел
+// удалён или дескриптор выдан другим списком
+#[derive(Clone)]
+pub struct NodeHandle {
+    node: NodeRef,
+    owner: Rc<()>,
+}
+
+// Узлы меняются только через &mut DoublyList, поэтому пока список заимствован
+// по общей ссылке, их можно читать без удерживаемого Ref. try_borrow_unguarded
+// всё равно проверяет, что узел не заимствован на запись: вместо UB будет паника.
+fn node_ref(link: &NodeRef) -> &DNode {
+    unsafe { link.try_borrow_unguarded() }.expect("list node is borrowed mutably")
+}
+
+// Итератор по ссылкам; len не даёт концам пройти друг через друга
+pub struct DoublyListIter<'a> {
+    head: Option<&'a Rc<RefCell<DNode>>>,
+    tail: Option<&'a Rc<RefCell<DNode>>>,
+    len: usize,
+}
+
+impl<'a> Iterator for DoublyListIter<'a> {
+    type Item = &'a String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.head.map(|node| {
+            let node = node_ref(node);
+            self.len -= 1;
+            self.head = node.next.as_ref();
+            &node.data
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a> DoubleEndedIterator for DoublyListIter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.tail.map(|node| {
+            let node = node_ref(node);
+            self.len -= 1;
+            self.tail = node.prev.as_ref();
+            &node.data
+        })
+    }
+}
+
+impl<'a> ExactSizeIterator for DoublyListIter<'a> {}
+
+impl<'a> std::iter::FusedIterator for DoublyListIter<'a> {}
+
+pub struct DoublyListIterMut<'a> {
+    current: Option<&'a Rc<RefCell<DNode>>>,
+}
+
+impl<'a> Iterator for DoublyListIterMut<'a> {
+    type Item = &'a mut String;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.current.take().map(|node| {
-            let data = node.borrow().data.clone();
-            self.current = node.borrow().next.clone();
+            // Список заимствован эксклюзивно, каждый узел выдаётся один раз;
+            // живых Ref/RefMut на узел быть не должно
+            assert!(node.try_borrow_mut().is_ok(), "list node is already borrowed");
+            let DNode { data, next, .. } = unsafe { &mut *node.as_ptr() };
+            let next: &'a Link = next;
+            self.current = next.as_ref();
             data
         })
     }
-}
\ No newline at end of file
+}
+
+pub struct DoublyListIntoIter {
+    list: DoublyList,
+}
+
+impl Iterator for DoublyListIntoIter {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_head()
+    }
+}
+
+impl DoubleEndedIterator for DoublyListIntoIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.pop_tail()
+    }
+}
+
+impl IntoIterator for DoublyList {
+    type Item = String;
+    type IntoIter = DoublyListIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        DoublyListIntoIter { list: self }
+    }
+}
+
+impl<'a> IntoIterator for &'a DoublyList {
+    type Item = &'a String;
+    type IntoIter = DoublyListIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut DoublyList {
+    type Item = &'a mut String;
+    type IntoIter = DoublyListIterMut<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl FromIterator<String> for DoublyList {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        let mut list = DoublyList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl Extend<String> for DoublyList {
+    fn extend<I: IntoIterator<Item = String>>(&mut self, iter: I) {
+        for value in iter {
+            self.add_tail(value);
+        }
+    }
+}
+
+// Курсор только для чтения. Между хвостом и головой находится "призрачная"
+// позиция, через которую курсор переходит с одного конца списка на другой.
+pub struct Cursor<'a> {
+    list: &'a DoublyList,
+    current: Option<&'a DNode>,
+    index: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        match self.current.take() {
+            Some(node) => {
+                self.current = node.next.as_ref().map(node_ref);
+                self.index += 1;
+            }
+            None => {
+                self.current = self.list.head.as_ref().map(node_ref);
+                self.index = 0;
+            }
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        match self.current.take() {
+            Some(node) => {
+                self.current = node.prev.as_ref().map(node_ref);
+                self.index = self.index.wrapping_sub(1);
+            }
+            None => {
+                self.current = self.list.tail.as_ref().map(node_ref);
+                self.index = self.list.size.saturating_sub(1);
+            }
+        }
+    }
+
+    pub fn current(&self) -> Option<&'a String> {
+        self.current.map(|node| &node.data)
+    }
+
+    pub fn peek_next(&self) -> Option<&'a String> {
+        let next = match self.current {
+            Some(node) => node.next.as_ref(),
+            None => self.list.head.as_ref(),
+        };
+        next.map(|node| &node_ref(node).data)
+    }
+
+    pub fn peek_prev(&self) -> Option<&'a String> {
+        let prev = match self.current {
+            Some(node) => node.prev.as_ref(),
+            None => self.list.tail.as_ref(),
+        };
+        prev.map(|node| &node_ref(node).data)
+    }
+}
+
+// Курсор для редактирования на месте
+pub struct CursorMut<'a> {
+    list: &'a mut DoublyList,
+    current: Link,
+    index: usize,
+}
+
+impl<'a> CursorMut<'a> {
+    pub fn index(&self) -> Option<usize> {
+        self.current.as_ref().map(|_| self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        match self.current.take() {
+            Some(node) => {
+                self.current = node.borrow().next.clone();
+                self.index += 1;
+            }
+            None => {
+                self.current = self.list.head.clone();
+                self.index = 0;
+            }
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        match self.current.take() {
+            Some(node) => {
+                self.current = node.borrow().prev.clone();
+                self.index = self.index.wrapping_sub(1);
+            }
+            None => {
+                self.current = self.list.tail.clone();
+                self.index = self.list.size.saturating_sub(1);
+            }
+        }
+    }
+
+    pub fn current(&mut self) -> Option<RefMut<'_, String>> {
+        self.current
+            .as_ref()
+            .map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.data))
+    }
+
+    pub fn peek_next(&self) -> Option<&String> {
+        let next = match &self.current {
+            Some(node) => node_ref(node).next.as_ref(),
+            None => self.list.head.as_ref(),
+        };
+        next.map(|node| &node_ref(node).data)
+    }
+
+    pub fn peek_prev(&self) -> Option<&String> {
+        let prev = match &self.current {
+            Some(node) => node_ref(node).prev.as_ref(),
+            None => self.list.tail.as_ref(),
+        };
+        prev.map(|node| &node_ref(node).data)
+    }
+
+    // Соседи текущей позиции; для призрачной позиции это хвост и голова
+    fn neighbours(&self) -> (Link, Link) {
+        match &self.current {
+            Some(node) => {
+                let node = node.borrow();
+                (node.prev.clone(), node.next.clone())
+            }
+            None => (self.list.tail.clone(), self.list.head.clone()),
+        }
+    }
+
+    // В призрачной позиции вставляет в начало списка
+    pub fn insert_after(&mut self, value: String) {
+        match self.current.clone() {
+            Some(node) => {
+                let (_, next) = self.neighbours();
+                self.list.link_value_between(Some(node), next, value);
+            }
+            None => self
+                .list
+                .link_value_between(None, self.list.head.clone(), value),
+        }
+    }
+
+    // В призрачной позиции вставляет в конец списка
+    pub fn insert_before(&mut self, value: String) {
+        match self.current.clone() {
+            Some(node) => {
+                let (prev, _) = self.neighbours();
+                self.list.link_value_between(prev, Some(node), value);
+                self.index += 1;
+            }
+            None => self
+                .list
+                .link_value_between(self.list.tail.clone(), None, value),
+        }
+    }
+
+    // Удаляет текущий элемент, курсор переходит на следующий
+    pub fn remove_current(&mut self) -> Option<String> {
+        let node = self.current.take()?;
+        self.current = node.borrow().next.clone();
+        Some(self.list.unlink(&node))
+    }
+
+    // Переносит все элементы other после текущего за O(1)
+    pub fn splice_after(&mut self, mut other: DoublyList) {
+        if let Some((head, tail, count)) = other.take_chain() {
+            match self.current.clone() {
+                Some(node) => {
+                    let (_, next) = self.neighbours();
+                    self.list.link_between(Some(node), next, head, tail, count);
+                }
+                None => {
+                    let next = self.list.head.clone();
+                    self.list.link_between(None, next, head, tail, count);
+                }
+            }
+        }
+    }
+
+    // Переносит все элементы other перед текущим за O(1)
+    pub fn splice_before(&mut self, mut other: DoublyList) {
+        if let Some((head, tail, count)) = other.take_chain() {
+            match self.current.clone() {
+                Some(node) => {
+                    let (prev, _) = self.neighbours();
+                    self.list.link_between(prev, Some(node), head, tail, count);
+                    self.index += count;
+                }
+                None => {
+                    let prev = self.list.tail.clone();
+                    self.list.link_between(prev, None, head, tail, count);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(values: &[&str]) -> DoublyList {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn values(list: &DoublyList) -> Vec<&str> {
+        list.iter().map(String::as_str).collect()
+    }
+
+    // Проверяет связи в обе стороны: проход от хвоста по prev даёт те же узлы
+    fn assert_links(list: &DoublyList) {
+        let mut backward = Vec::new();
+        let mut cursor = list.cursor_back();
+        while let Some(value) = cursor.current() {
+            backward.push(value.as_str());
+            cursor.move_prev();
+        }
+        backward.reverse();
+        assert_eq!(backward, values(list));
+        assert_eq!(backward.len(), list.len());
+    }
+
+    #[test]
+    fn rotate_moves_elements_between_ends() {
+        let mut list = list(&["a", "b", "c", "d"]);
+        list.rotate_left(1);
+        assert_eq!(values(&list), ["b", "c", "d", "a"]);
+        list.rotate_right(2);
+        assert_eq!(values(&list), ["d", "a", "b", "c"]);
+        list.rotate_left(6);
+        assert_eq!(values(&list), ["b", "c", "d", "a"]);
+        list.rotate_right(4);
+        assert_eq!(values(&list), ["b", "c", "d", "a"]);
+        assert_links(&list);
+    }
+
+    #[test]
+    fn rotate_empty_list_is_noop() {
+        let mut list = DoublyList::new();
+        list.rotate_left(3);
+        list.rotate_right(3);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn reverse_relinks_both_directions() {
+        let mut list = list(&["a", "b", "c"]);
+        list.reverse();
+        assert_eq!(values(&list), ["c", "b", "a"]);
+        assert_links(&list);
+        list.add_tail("z".to_string());
+        list.add_head("0".to_string());
+        assert_eq!(values(&list), ["0", "c", "b", "a", "z"]);
+        assert_links(&list);
+
+        let mut empty = DoublyList::new();
+        empty.reverse();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn swap_exchanges_values() {
+        let mut list = list(&["a", "b", "c"]);
+        list.swap(0, 2).unwrap();
+        assert_eq!(values(&list), ["c", "b", "a"]);
+        list.swap(1, 1).unwrap();
+        assert_eq!(values(&list), ["c", "b", "a"]);
+        assert_eq!(list.swap(0, 3), Err("Index out of range".to_string()));
+        assert_eq!(values(&list), ["c", "b", "a"]);
+    }
+
+    fn text(value: Option<&String>) -> Option<&str> {
+        value.map(String::as_str)
+    }
+
+    #[test]
+    fn cursor_walks_through_ghost_position() {
+        let list = list(&["a", "b", "c"]);
+        let mut cursor = list.cursor_front();
+        assert_eq!(text(cursor.current()), Some("a"));
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(text(cursor.peek_prev()), None);
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(text(cursor.current()), Some("c"));
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(text(cursor.peek_next()), Some("a"));
+        assert_eq!(text(cursor.peek_prev()), Some("c"));
+        cursor.move_next();
+        assert_eq!(text(cursor.current()), Some("a"));
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!(text(cursor.current()), Some("c"));
+        assert_eq!(cursor.index(), Some(2));
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(text(cursor.peek_next()), Some("c"));
+        assert_eq!(text(cursor.peek_prev()), Some("a"));
+    }
+
+    #[test]
+    fn cursor_on_empty_list_stays_on_ghost() {
+        let list = DoublyList::new();
+        let mut cursor = list.cursor_back();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        cursor.move_prev();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), None);
+    }
+
+    #[test]
+    fn cursor_mut_inserts_and_removes() {
+        let mut list = list(&["a", "c"]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.insert_before("b".to_string());
+        assert_eq!(cursor.index(), Some(2));
+        cursor.insert_after("d".to_string());
+        assert_eq!(cursor.remove_current(), Some("c".to_string()));
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current().as_deref().map(String::as_str), Some("d"));
+        cursor.move_next();
+        cursor.insert_before("e".to_string());
+        cursor.insert_after("z".to_string());
+        assert_eq!(cursor.remove_current(), None);
+        assert_eq!(text(cursor.peek_next()), Some("z"));
+        assert_eq!(text(cursor.peek_prev()), Some("e"));
+        assert_eq!(values(&list), ["z", "a", "b", "d", "e"]);
+        assert_links(&list);
+    }
+
+    #[test]
+    fn cursor_mut_removes_tail_and_head() {
+        let mut list = list(&["a", "b", "c"]);
+        let mut cursor = list.cursor_back_mut();
+        assert_eq!(cursor.remove_current(), Some("c".to_string()));
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some("a".to_string()));
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(values(&list), ["b"]);
+        assert_links(&list);
+        list.add_tail("z".to_string());
+        list.add_head("0".to_string());
+        assert_eq!(values(&list), ["0", "b", "z"]);
+        assert_links(&list);
+    }
+
+    #[test]
+    fn cursor_mut_splices_lists() {
+        let mut list = list(&["a", "d"]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.splice_before(["b", "c"].iter().map(|v| v.to_string()).collect());
+        assert_eq!(cursor.index(), Some(3));
+        cursor.splice_after(["e"].iter().map(|v| v.to_string()).collect());
+        cursor.splice_after(DoublyList::new());
+        cursor.move_next();
+        cursor.move_next();
+        cursor.splice_after(["0"].iter().map(|v| v.to_string()).collect());
+        cursor.splice_before(["f"].iter().map(|v| v.to_string()).collect());
+        assert_eq!(values(&list), ["0", "a", "b", "c", "d", "e", "f"]);
+        assert_links(&list);
+    }
+
+    #[test]
+    fn cursor_mut_edits_current_value() {
+        let mut list = list(&["a", "b"]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.current().unwrap().push('!');
+        cursor.move_next();
+        *cursor.current().unwrap() = "B".to_string();
+        assert_eq!(values(&list), ["a!", "B"]);
+    }
+
+    #[test]
+    fn cursors_and_iterators_interleave() {
+        let mut list = list(&["a", "b", "c"]);
+        {
+            let mut front = list.cursor_front();
+            let mut back = list.cursor_back();
+            let mut iter = list.iter();
+            assert_eq!(text(iter.next()), text(front.current()));
+            back.move_prev();
+            assert_eq!(text(iter.next_back()), text(back.peek_next()));
+            front.move_next();
+            assert_eq!(text(iter.next()), text(back.current()));
+            assert_eq!(text(front.current()), Some("b"));
+            assert_eq!(iter.next(), None);
+        }
+        {
+            let mut cursor = list.cursor_front_mut();
+            cursor.current().unwrap().push('1');
+            assert_eq!(text(cursor.peek_next()), Some("b"));
+            cursor.move_next();
+            cursor.remove_current();
+            assert_eq!(text(cursor.peek_prev()), Some("a1"));
+        }
+        for value in list.iter_mut() {
+            value.push('2');
+        }
+        let cursor = list.cursor_front();
+        let mut iter = list.iter();
+        assert_eq!(text(iter.next()), Some("a12"));
+        assert_eq!(text(cursor.peek_next()), Some("c2"));
+        assert_eq!(text(iter.next()), Some("c2"));
+        assert_links(&list);
+    }
+
+    #[test]
+    fn iterators_work_from_both_ends() {
+        let mut list = list(&["a", "b", "c", "d"]);
+        let mut iter = list.iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(text(iter.next()), Some("a"));
+        assert_eq!(text(iter.next_back()), Some("d"));
+        assert_eq!(text(iter.next_back()), Some("c"));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(text(iter.next()), Some("b"));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        for value in &mut list {
+            value.push('!');
+        }
+        let mut owned = list.into_iter();
+        assert_eq!(owned.next_back(), Some("d!".to_string()));
+        assert_eq!(owned.collect::<Vec<_>>(), ["a!", "b!", "c!"]);
+    }
+
+    #[test]
+    fn collect_and_extend_append_to_tail() {
+        let mut list = list(&["a"]);
+        list.extend(["b".to_string(), "c".to_string()]);
+        assert_eq!(values(&list), ["a", "b", "c"]);
+        assert_links(&list);
+        let reversed: DoublyList = list.into_iter().rev().collect();
+        assert_eq!(values(&reversed), ["c", "b", "a"]);
+
+        let empty = DoublyList::new();
+        assert_eq!(empty.iter().next_back(), None);
+        assert_eq!(empty.iter().len(), 0);
+    }
+
+    #[test]
+    fn node_handles_move_and_remove_nodes() {
+        let mut list = list(&["b"]);
+        let a = list.push_head_node("a".to_string());
+        let z = list.push_head_node("z".to_string());
+        assert_eq!(list.move_node_to_head(&a), Ok(()));
+        assert_eq!(values(&list), ["a", "z", "b"]);
+        assert_eq!(list.move_node_to_head(&a), Ok(()));
+        assert_eq!(list.remove_node(z), Ok("z".to_string()));
+        assert_eq!(values(&list), ["a", "b"]);
+        assert_links(&list);
+    }
+
+    #[test]
+    fn removed_node_handle_is_rejected() {
+        let mut list = list(&["b"]);
+        let a = list.push_head_node("a".to_string());
+        assert_eq!(list.remove_node(a.clone()), Ok("a".to_string()));
+        let error = Err("Node does not belong to this list".to_string());
+        assert_eq!(list.move_node_to_head(&a), error);
+        assert_eq!(list.remove_node(a), error.map(|_| String::new()));
+        assert_eq!(values(&list), ["b"]);
+        assert_links(&list);
+
+        // Единственный узел, удалённый через курсор
+        let mut single = DoublyList::new();
+        let only = single.push_head_node("x".to_string());
+        single.cursor_front_mut().remove_current();
+        assert!(single.move_node_to_head(&only).is_err());
+        assert!(single.is_empty());
+    }
+
+    #[test]
+    fn foreign_node_handle_is_rejected() {
+        let mut first = list(&["c"]);
+        let mut second = list(&["x", "y"]);
+        let middle = first.push_head_node("b".to_string());
+        first.add_head("a".to_string());
+        assert!(second.move_node_to_head(&middle).is_err());
+        assert!(second.remove_node(middle.clone()).is_err());
+        assert_eq!(values(&second), ["x", "y"]);
+        assert_eq!(second.len(), 2);
+
+        // После переноса узлов в другой список старые дескрипторы недействительны
+        second.append(&mut first);
+        assert!(first.remove_node(middle.clone()).is_err());
+        assert!(second.remove_node(middle).is_err());
+        assert_eq!(values(&second), ["x", "y", "a", "b", "c"]);
+        assert!(first.is_empty());
+        assert_links(&second);
+    }
+
+    #[test]
+    fn remove_all_counts_removed() {
+        let mut list = list(&["x", "a", "x", "b", "x"]);
+        assert_eq!(list.remove_all("x"), 3);
+        assert_eq!(values(&list), ["a", "b"]);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.remove_all("x"), 0);
+        assert_links(&list);
+    }
+
+    #[test]
+    fn retain_keeps_matching_elements() {
+        let mut list = list(&["1", "2", "3", "4"]);
+        assert_eq!(list.retain(|value| value != "4" && value != "1"), 2);
+        assert_eq!(values(&list), ["2", "3"]);
+        assert_links(&list);
+        assert_eq!(list.retain(|_| false), 2);
+        assert!(list.is_empty());
+        assert_links(&list);
+    }
+
+    #[test]
+    fn drain_filter_returns_removed_in_order() {
+        let mut list = list(&["a1", "b", "a2", "c", "a3"]);
+        assert_eq!(list.drain_filter(|value| value.starts_with('a')), ["a1", "a2", "a3"]);
+        assert_eq!(values(&list), ["b", "c"]);
+        assert_links(&list);
+        assert!(list.drain_filter(|_| false).is_empty());
+
+        let mut empty = DoublyList::new();
+        assert!(empty.drain_filter(|_| true).is_empty());
+    }
+
+    #[test]
+    fn append_moves_all_elements() {
+        let mut first = list(&["a", "b"]);
+        let mut second = list(&["c", "d"]);
+        first.append(&mut second);
+        assert_eq!(values(&first), ["a", "b", "c", "d"]);
+        assert_eq!(first.len(), 4);
+        assert!(second.is_empty());
+        assert_links(&first);
+        assert_links(&second);
+
+        let mut empty = DoublyList::new();
+        empty.append(&mut first);
+        first.append(&mut DoublyList::new());
+        assert_eq!(values(&empty), ["a", "b", "c", "d"]);
+        assert!(first.is_empty());
+        assert_links(&empty);
+    }
+
+    #[test]
+    fn split_off_keeps_both_halves_consistent() {
+        let mut first = list(&["a", "b", "c", "d"]);
+        let mut rest = first.split_off(1).unwrap();
+        assert_eq!(values(&first), ["a"]);
+        assert_eq!(values(&rest), ["b", "c", "d"]);
+        assert_eq!((first.len(), rest.len()), (1, 3));
+        assert_links(&first);
+        assert_links(&rest);
+
+        let tail = rest.split_off(3).unwrap();
+        assert!(tail.is_empty());
+        assert_eq!(rest.len(), 3);
+        assert_links(&tail);
+        let all = rest.split_off(0).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(values(&all), ["b", "c", "d"]);
+        assert_links(&all);
+        assert_links(&rest);
+
+        assert_eq!(first.split_off(2).err(), Some("Index out of range".to_string()));
+        assert_eq!(values(&first), ["a"]);
+    }
+}
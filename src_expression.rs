@@ -0,0 +1,323 @@
+use std::fmt;
+
+use crate::stack::Stack;
+
+// Вычисление арифметических выражений на основе стека: инфиксная запись
+// переводится в обратную польскую (алгоритм сортировочной станции), затем вычисляется.
+
+// Внутреннее обозначение унарного минуса в ОПЗ
+const NEG: &str = "neg";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+impl Number {
+    pub fn parse(token: &str) -> Option<Number> {
+        if let Ok(value) = token.parse::<i64>() {
+            return Some(Number::Int(value));
+        }
+        token.parse::<f64>().ok().map(Number::Float)
+    }
+
+    fn as_f64(self) -> f64 {
+        match self {
+            Number::Int(value) => value as f64,
+            Number::Float(value) => value,
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Number::Int(value) => write!(f, "{}", value),
+            // {:?} сохраняет дробную часть, чтобы 3.0 не превратилось в целое
+            Number::Float(value) => write!(f, "{:?}", value),
+        }
+    }
+}
+
+fn is_operator(token: &str) -> bool {
+    matches!(token, "+" | "-" | "*" | "/" | "%" | "^" | NEG)
+}
+
+fn precedence(operator: &str) -> u8 {
+    match operator {
+        "+" | "-" => 1,
+        "*" | "/" | "%" => 2,
+        NEG => 3,
+        "^" => 4,
+        _ => 0,
+    }
+}
+
+fn is_right_associative(operator: &str) -> bool {
+    matches!(operator, "^" | NEG)
+}
+
+// Состояние стека от дна к вершине
+fn stack_state(stack: &Stack) -> String {
+    let mut values: Vec<&str> = stack.iter().map(|value| value.as_str()).collect();
+    values.reverse();
+    format!("[{}]", values.join(" "))
+}
+
+// Разбивает выражение на числа, операторы и скобки; унарный минус становится NEG
+pub fn tokenize(expression: &str) -> Result<Vec<String>, String> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens: Vec<String> = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let number: String = chars[start..i].iter().collect();
+            if Number::parse(&number).is_none() {
+                return Err(format!("Invalid number \"{}\" at position {}", number, start));
+            }
+            tokens.push(number);
+            continue;
+        }
+
+        // Минус или плюс в начале, после оператора или "(" — унарный
+        let unary = tokens
+            .last()
+            .is_none_or(|last| last == "(" || is_operator(last));
+
+        match c {
+            '-' if unary => tokens.push(NEG.to_string()),
+            '+' if unary => {}
+            '+' | '-' | '*' | '/' | '%' | '^' | '(' | ')' => tokens.push(c.to_string()),
+            _ => return Err(format!("Unexpected character '{}' at position {}", c, i)),
+        }
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+// Алгоритм сортировочной станции; в trace пишется состояние после каждого токена
+pub fn to_rpn(expression: &str, trace: &mut Vec<String>) -> Result<Vec<String>, String> {
+    let mut operators = Stack::new(16);
+    let mut output: Vec<String> = Vec::new();
+
+    for token in tokenize(expression)? {
+        match token.as_str() {
+            "(" | NEG => operators.push(token.clone())?,
+            ")" => loop {
+                match operators.pop() {
+                    Ok(top) if top == "(" => break,
+                    Ok(top) => output.push(top),
+                    Err(_) => return Err("Mismatched parentheses".to_string()),
+                }
+            },
+            operator if is_operator(operator) => {
+                while let Ok(top) = operators.peek() {
+                    let pops = top != "("
+                        && (precedence(top) > precedence(operator)
+                            || (precedence(top) == precedence(operator)
+                                && !is_right_associative(operator)));
+                    if !pops {
+                        break;
+                    }
+                    output.push(operators.pop()?);
+                }
+                operators.push(token.clone())?;
+            }
+            _ => output.push(token.clone()),
+        }
+
+        trace.push(format!(
+            "{:>6} | operators {} | output {}",
+            token,
+            stack_state(&operators),
+            output.join(" ")
+        ));
+    }
+
+    while let Ok(top) = operators.pop() {
+        if top == "(" {
+            return Err("Mismatched parentheses".to_string());
+        }
+        output.push(top);
+    }
+
+    Ok(output)
+}
+
+fn apply(operator: &str, a: Number, b: Number) -> Result<Number, String> {
+    let overflow = || "Integer overflow".to_string();
+
+    if let (Number::Int(x), Number::Int(y)) = (a, b) {
+        return match operator {
+            "+" => x.checked_add(y).map(Number::Int).ok_or_else(overflow),
+            "-" => x.checked_sub(y).map(Number::Int).ok_or_else(overflow),
+            "*" => x.checked_mul(y).map(Number::Int).ok_or_else(overflow),
+            "/" if y == 0 => Err("Division by zero".to_string()),
+            // i64::MIN / -1 не помещается в i64, поэтому и остаток, и частное проверяются
+            "/" => match x.checked_rem(y) {
+                Some(0) => x.checked_div(y).map(Number::Int).ok_or_else(overflow),
+                Some(_) => Ok(Number::Float(x as f64 / y as f64)),
+                None => Err(overflow()),
+            },
+            "%" if y == 0 => Err("Division by zero".to_string()),
+            "%" => x.checked_rem(y).map(Number::Int).ok_or_else(overflow),
+            "^" if y >= 0 => u32::try_from(y)
+                .ok()
+                .and_then(|exponent| x.checked_pow(exponent))
+                .map(Number::Int)
+                .ok_or_else(overflow),
+            "^" => Ok(Number::Float((x as f64).powf(y as f64))),
+            _ => Err(format!("Unknown operator \"{}\"", operator)),
+        };
+    }
+
+    let (x, y) = (a.as_f64(), b.as_f64());
+    match operator {
+        "+" => Ok(Number::Float(x + y)),
+        "-" => Ok(Number::Float(x - y)),
+        "*" => Ok(Number::Float(x * y)),
+        "/" | "%" if y == 0.0 => Err("Division by zero".to_string()),
+        "/" => Ok(Number::Float(x / y)),
+        "%" => Ok(Number::Float(x % y)),
+        "^" => Ok(Number::Float(x.powf(y))),
+        _ => Err(format!("Unknown operator \"{}\"", operator)),
+    }
+}
+
+fn pop_number(stack: &mut Stack) -> Result<Number, String> {
+    let value = stack
+        .pop()
+        .map_err(|_| "Malformed expression: not enough operands".to_string())?;
+    Number::parse(&value).ok_or_else(|| format!("Invalid number \"{}\"", value))
+}
+
+// Вычисление ОПЗ; в trace пишется состояние стека после каждого токена
+pub fn eval_rpn(tokens: &[String], trace: &mut Vec<String>) -> Result<Number, String> {
+    let mut stack = Stack::new(16);
+
+    for token in tokens {
+        let token = token.as_str();
+        let result = if token == NEG {
+            match pop_number(&mut stack)? {
+                Number::Int(x) => Number::Int(x.checked_neg().ok_or("Integer overflow")?),
+                Number::Float(x) => Number::Float(-x),
+            }
+        } else if is_operator(token) {
+            let b = pop_number(&mut stack)?;
+            let a = pop_number(&mut stack)?;
+            apply(token, a, b)?
+        } else {
+            Number::parse(token).ok_or_else(|| format!("Unknown token \"{}\"", token))?
+        };
+
+        stack.push(result.to_string())?;
+        trace.push(format!("{:>6} | stack {}", token, stack_state(&stack)));
+    }
+
+    let result = pop_number(&mut stack)?;
+    if !stack.is_empty() {
+        return Err("Malformed expression: too many operands".to_string());
+    }
+    Ok(result)
+}
+
+pub fn eval_infix(expression: &str, trace: &mut Vec<String>) -> Result<Number, String> {
+    let rpn = to_rpn(expression, trace)?;
+    eval_rpn(&rpn, trace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(expression: &str) -> Vec<String> {
+        expression.split_whitespace().map(|token| token.to_string()).collect()
+    }
+
+    fn rpn(expression: &str) -> Result<Number, String> {
+        eval_rpn(&tokens(expression), &mut Vec::new())
+    }
+
+    fn infix(expression: &str) -> Result<Number, String> {
+        eval_infix(expression, &mut Vec::new())
+    }
+
+    #[test]
+    fn tokenize_marks_unary_minus() {
+        assert_eq!(tokenize("-2*(+3 - -1)").unwrap(), ["neg", "2", "*", "(", "3", "-", "neg", "1", ")"]);
+        assert_eq!(tokenize("2 $ 3"), Err("Unexpected character '$' at position 2".to_string()));
+        assert_eq!(tokenize("1.2.3"), Err("Invalid number \"1.2.3\" at position 0".to_string()));
+    }
+
+    #[test]
+    fn to_rpn_respects_precedence_and_associativity() {
+        let mut trace = Vec::new();
+        assert_eq!(to_rpn("1 + 2 * 3", &mut trace).unwrap(), ["1", "2", "3", "*", "+"]);
+        assert_eq!(trace.len(), 5);
+        assert_eq!(to_rpn("2 ^ 3 ^ 2", &mut Vec::new()).unwrap(), ["2", "3", "2", "^", "^"]);
+        assert_eq!(to_rpn("8 - 3 - 1", &mut Vec::new()).unwrap(), ["8", "3", "-", "1", "-"]);
+        assert_eq!(to_rpn("-2 ^ 2", &mut Vec::new()).unwrap(), ["2", "2", "^", "neg"]);
+        assert_eq!(to_rpn("(1 + 2", &mut Vec::new()), Err("Mismatched parentheses".to_string()));
+        assert_eq!(to_rpn("1 + 2)", &mut Vec::new()), Err("Mismatched parentheses".to_string()));
+    }
+
+    #[test]
+    fn evaluates_integers_and_floats() {
+        assert_eq!(infix("(1 + 2) * 4 - 5"), Ok(Number::Int(7)));
+        assert_eq!(infix("7 / 2"), Ok(Number::Float(3.5)));
+        assert_eq!(infix("8 / -2"), Ok(Number::Int(-4)));
+        assert_eq!(infix("7 % 3"), Ok(Number::Int(1)));
+        assert_eq!(infix("2 ^ -1"), Ok(Number::Float(0.5)));
+        assert_eq!(infix("1.5 * 2"), Ok(Number::Float(3.0)));
+        assert_eq!(Number::Float(3.0).to_string(), "3.0");
+        assert_eq!(rpn("3 4 + 2 *"), Ok(Number::Int(14)));
+    }
+
+    #[test]
+    fn reports_division_by_zero() {
+        assert_eq!(infix("1 / 0"), Err("Division by zero".to_string()));
+        assert_eq!(infix("1 % 0"), Err("Division by zero".to_string()));
+        assert_eq!(infix("1.5 / 0"), Err("Division by zero".to_string()));
+    }
+
+    #[test]
+    fn reports_integer_overflow() {
+        let overflow = Err("Integer overflow".to_string());
+        assert_eq!(rpn("-9223372036854775808 -1 /"), overflow);
+        assert_eq!(rpn("-9223372036854775808 -1 %"), overflow);
+        assert_eq!(rpn("9223372036854775807 1 +"), overflow);
+        assert_eq!(rpn("-9223372036854775808 1 -"), overflow);
+        assert_eq!(rpn("4611686018427387904 2 *"), overflow);
+        assert_eq!(rpn("2 63 ^"), overflow);
+        assert_eq!(rpn("-9223372036854775808 neg"), overflow);
+        assert_eq!(rpn("-9223372036854775808 1 /"), Ok(Number::Int(i64::MIN)));
+    }
+
+    #[test]
+    fn reports_malformed_expressions() {
+        assert_eq!(rpn("1 +"), Err("Malformed expression: not enough operands".to_string()));
+        assert_eq!(rpn("1 2"), Err("Malformed expression: too many operands".to_string()));
+        assert_eq!(rpn(""), Err("Malformed expression: not enough operands".to_string()));
+        assert_eq!(rpn("1 x +"), Err("Unknown token \"x\"".to_string()));
+    }
+
+    #[test]
+    fn trace_records_stack_after_each_token() {
+        let mut trace = Vec::new();
+        eval_rpn(&tokens("1 2 +"), &mut trace).unwrap();
+        assert_eq!(trace, ["     1 | stack [1]", "     2 | stack [1 2]", "     + | stack [3]"]);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::expression;
+use crate::output::{CommandResult, ErrorKind, Value};
+use crate::registry::{Call, CommandSpec, DataStructures, Module};
+
+// Вычисление выражений на стеке; состояния и файлов нет
+pub struct ExpressionModule;
+
+const COMMANDS: &[CommandSpec] = &[
+    CommandSpec { name: "EVAL", signature: "<expression...>", help: "Evaluate an infix expression", lookup: true },
+    CommandSpec { name: "EVAL_RPN", signature: "<tokens...>", help: "Evaluate an expression in reverse Polish notation", lookup: true },
+    CommandSpec { name: "EVAL_TRACE", signature: "<expression...>", help: "Evaluate an infix expression step by step", lookup: true },
+];
+
+impl Module for ExpressionModule {
+    fn title(&self) -> &'static str {
+        "Expressions"
+    }
+
+    fn name(&self) -> &'static str {
+        "expression"
+    }
+
+    fn commands(&self) -> &'static [CommandSpec] {
+        COMMANDS
+    }
+
+    fn execute(&mut self, call: &Call, _ds: &mut DataStructures, out: &mut CommandResult) {
+        let parts = call.parts;
+        match call.command {
+            "EVAL" => {
+                let expression = parts[1..].join(" ");
+                let mut trace = Vec::new();
+                match expression::to_rpn(&expression, &mut trace) {
+                    Ok(rpn) => {
+                        out.line(format!("RPN: {}", rpn.join(" ")));
+                        match expression::eval_rpn(&rpn, &mut trace) {
+                            Ok(result) => {
+                                out.line(format!("Result: {}", result));
+                                out.set_value(Value::Number(result.to_string()));
+                            }
+                            Err(e) => out.fail_as(ErrorKind::Failed, e),
+                        }
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Failed, e),
+                }
+            }
+            "EVAL_RPN" => {
+                let tokens: Vec<String> = parts[1..].iter().map(|token| token.to_string()).collect();
+                match expression::eval_rpn(&tokens, &mut Vec::new()) {
+                    Ok(result) => {
+                        out.line(format!("Result: {}", result));
+                        out.set_value(Value::Number(result.to_string()));
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Failed, e),
+                }
+            }
+            "EVAL_TRACE" => {
+                let expression = parts[1..].join(" ");
+                let mut trace = Vec::new();
+                let result = expression::eval_infix(&expression, &mut trace);
+                out.lines.extend(trace.iter().cloned());
+                out.set_values(trace);
+                match result {
+                    Ok(result) => {
+                        out.line(format!("Result: {}", result));
+                        out.set_value(Value::Number(result.to_string()));
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Failed, e),
+                }
+            }
+            _ => unreachable!("{} is not an expression command", call.command),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::ErrorKind;
+    use crate::testing::{printed, run, structures};
+
+    #[test]
+    fn eval_commands_print_results() {
+        let mut ds = structures();
+        assert_eq!(printed(&mut ds, "EVAL (1 + 2) * 3"), "RPN: 1 2 + 3 *\nResult: 9");
+        assert_eq!(printed(&mut ds, "EVAL_RPN 7 2 /"), "Result: 3.5");
+        let out = run(&mut ds, &["EVAL_TRACE 1 + 2"]);
+        assert_eq!(out.lines.last().map(String::as_str), Some("Result: 3"));
+    }
+
+    #[test]
+    fn overflow_is_an_error_not_a_panic() {
+        let mut ds = structures();
+        let out = run(&mut ds, &["EVAL_RPN -9223372036854775808 -1 /"]);
+        assert_eq!(out.lines, ["Error: Integer overflow"]);
+        assert_eq!(out.kind, Some(ErrorKind::Failed));
+    }
+}
@@ -1,5 +1,6 @@
 use std::rc::Rc;
 use std::cell::RefCell;
+use std::collections::VecDeque;
 
 // Полное бинарное дерево
 type TreeLink = Option<Rc<RefCell<TreeNode>>>;
@@ -12,11 +13,23 @@ pub struct TreeNode {
 
 pub struct FullBinaryTree {
     root: TreeLink,
+    size: usize,
 }
 
 impl FullBinaryTree {
     pub fn new() -> Self {
-        FullBinaryTree { root: None }
+        FullBinaryTree {
+            root: None,
+            size: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
     }
 
     pub fn insert(&mut self, value: String) {
@@ -26,21 +39,12 @@ impl FullBinaryTree {
                 left: None,
                 right: None,
             })));
+            self.size = 1;
             return;
         }
 
-        let node_count = Self::count_nodes(&self.root);
-        Self::insert_helper(&self.root, value, 0, node_count);
-    }
-
-    fn count_nodes(root: &TreeLink) -> usize {
-        match root {
-            None => 0,
-            Some(node) => {
-                let node = node.borrow();
-                1 + Self::count_nodes(&node.left) + Self::count_nodes(&node.right)
-            }
-        }
+        Self::insert_helper(&self.root, value, 0, self.size);
+        self.size += 1;
     }
 
     fn insert_helper(root: &TreeLink, value: String, index: usize, target_index: usize) -> bool {
@@ -117,46 +121,30 @@ impl FullBinaryTree {
         }
     }
 
-    pub fn print_inorder(&self) {
-        if self.root.is_none() {
-            println!("Дерево пустое");
-            return;
-        }
-
-        println!("Структура дерева:");
-        Self::print_tree_helper(&self.root, String::new(), false);
-
-        print!("Симметричный обход (inorder): ");
-        Self::print_inorder_linear(&self.root);
-        println!();
+    // Строки псевдографики со структурой дерева
+    pub fn structure_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        Self::structure_lines_helper(&self.root, String::new(), false, &mut lines);
+        lines
     }
 
-    fn print_tree_helper(root: &TreeLink, prefix: String, is_left: bool) {
+    fn structure_lines_helper(root: &TreeLink, prefix: String, is_left: bool, lines: &mut Vec<String>) {
         if let Some(node) = root {
             let node = node.borrow();
-            println!("{}{}{}", prefix, if is_left { "├──" } else { "└──" }, node.data);
+            lines.push(format!("{}{}{}", prefix, if is_left { "├──" } else { "└──" }, node.data));
 
             if node.left.is_some() || node.right.is_some() {
                 let new_prefix = format!("{}{}", prefix, if is_left { "│   " } else { "    " });
                 if node.left.is_some() {
-                    Self::print_tree_helper(&node.left, new_prefix.clone(), true);
+                    Self::structure_lines_helper(&node.left, new_prefix.clone(), true, lines);
                 }
                 if node.right.is_some() {
-                    Self::print_tree_helper(&node.right, new_prefix, false);
+                    Self::structure_lines_helper(&node.right, new_prefix, false, lines);
                 }
             }
         }
     }
 
-    fn print_inorder_linear(root: &TreeLink) {
-        if let Some(node) = root {
-            let node = node.borrow();
-            Self::print_inorder_linear(&node.left);
-            print!("{} ", node.data);
-            Self::print_inorder_linear(&node.right);
-        }
-    }
-
     pub fn collect_inorder(&self) -> Vec<String> {
         let mut result = Vec::new();
         Self::collect_inorder_helper(&self.root, &mut result);
@@ -171,4 +159,153 @@ impl FullBinaryTree {
             Self::collect_inorder_helper(&node.right, result);
         }
     }
-}
\ No newline at end of file
+
+    // Обход по уровням совпадает с порядком вставки
+    pub fn iter(&self) -> TreeIter<'_> {
+        TreeIter {
+            queue: self.root.iter().collect(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> TreeIterMut<'_> {
+        TreeIterMut {
+            queue: self.root.iter().collect(),
+        }
+    }
+}
+
+impl Default for FullBinaryTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct TreeIter<'a> {
+    queue: VecDeque<&'a Rc<RefCell<TreeNode>>>,
+}
+
+impl<'a> Iterator for TreeIter<'a> {
+    type Item = &'a String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+        // Узлы меняются только через &mut FullBinaryTree; проверка вместо UB
+        // даёт панику, если узел всё же заимствован на запись
+        let node = unsafe { node.try_borrow_unguarded() }.expect("tree node is borrowed mutably");
+        self.queue.extend(node.left.iter().chain(node.right.iter()));
+        Some(&node.data)
+    }
+}
+
+pub struct TreeIterMut<'a> {
+    queue: VecDeque<&'a Rc<RefCell<TreeNode>>>,
+}
+
+impl<'a> Iterator for TreeIterMut<'a> {
+    type Item = &'a mut String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+        // Дерево заимствовано эксклюзивно, каждый узел выдаётся один раз
+        assert!(node.try_borrow_mut().is_ok(), "tree node is already borrowed");
+        let TreeNode { data, left, right } = unsafe { &mut *node.as_ptr() };
+        let (left, right): (&'a TreeLink, &'a TreeLink) = (left, right);
+        self.queue.extend(left.iter().chain(right.iter()));
+        Some(data)
+    }
+}
+
+pub struct TreeIntoIter {
+    queue: VecDeque<Rc<RefCell<TreeNode>>>,
+}
+
+impl Iterator for TreeIntoIter {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+        let mut node = node.borrow_mut();
+        self.queue
+            .extend(node.left.take().into_iter().chain(node.right.take()));
+        Some(std::mem::take(&mut node.data))
+    }
+}
+
+impl IntoIterator for FullBinaryTree {
+    type Item = String;
+    type IntoIter = TreeIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        TreeIntoIter {
+            queue: self.root.into_iter().collect(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a FullBinaryTree {
+    type Item = &'a String;
+    type IntoIter = TreeIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut FullBinaryTree {
+    type Item = &'a mut String;
+    type IntoIter = TreeIterMut<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl FromIterator<String> for FullBinaryTree {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        let mut tree = FullBinaryTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl Extend<String> for FullBinaryTree {
+    fn extend<I: IntoIterator<Item = String>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(values: &[&str]) -> FullBinaryTree {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn iterators_follow_insertion_order() {
+        let mut tree = tree(&["a", "b", "c", "d", "e"]);
+        assert_eq!(tree.len(), 5);
+        assert_eq!(tree.iter().map(String::as_str).collect::<Vec<_>>(), ["a", "b", "c", "d", "e"]);
+        for value in &mut tree {
+            value.push('!');
+        }
+        assert_eq!((&tree).into_iter().next().map(String::as_str), Some("a!"));
+        let owned: Vec<String> = tree.into_iter().collect();
+        assert_eq!(owned, ["a!", "b!", "c!", "d!", "e!"]);
+    }
+
+    #[test]
+    fn extend_continues_level_order() {
+        let mut tree = tree(&["a"]);
+        tree.extend(["b".to_string(), "c".to_string(), "d".to_string()]);
+        let round_trip: FullBinaryTree = tree.into_iter().collect();
+        assert_eq!(round_trip.iter().map(String::as_str).collect::<Vec<_>>(), ["a", "b", "c", "d"]);
+
+        let empty = FullBinaryTree::new();
+        assert!(empty.is_empty());
+        assert_eq!(empty.iter().next(), None);
+    }
+}
@@ -0,0 +1,184 @@
+// История изменяющих команд для UNDO/REDO.
+// Каждая запись хранит исходную команду и команды, отменяющие её действие.
+#[derive(Clone)]
+pub struct HistoryEntry {
+    pub command: String,
+    pub inverse: Vec<String>,
+}
+
+#[derive(Clone)]
+pub struct History {
+    undo: Vec<HistoryEntry>,
+    redo: Vec<HistoryEntry>,
+    depth: usize,
+    // Во время выполнения UNDO/REDO команды не записываются
+    replaying: bool,
+}
+
+impl History {
+    pub fn new(depth: usize) -> Self {
+        History {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            depth,
+            replaying: false,
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    // Самые старые записи сверх глубины отбрасываются
+    pub fn set_depth(&mut self, depth: usize) {
+        self.depth = depth;
+        Self::trim(&mut self.undo, depth);
+        Self::trim(&mut self.redo, depth);
+    }
+
+    fn trim(entries: &mut Vec<HistoryEntry>, depth: usize) {
+        if entries.len() > depth {
+            entries.drain(..entries.len() - depth);
+        }
+    }
+
+    pub fn set_replaying(&mut self, replaying: bool) {
+        self.replaying = replaying;
+    }
+
+    // Новое изменение делает недоступными отменённые команды.
+    // Слова команды разделяются одним пробелом: табуляция разделяет поля в файле истории
+    pub fn record(&mut self, command: &str, inverse: Vec<String>) {
+        if self.replaying {
+            return;
+        }
+        self.redo.clear();
+        self.push_undo(HistoryEntry {
+            command: command.split_whitespace().collect::<Vec<_>>().join(" "),
+            inverse,
+        });
+    }
+
+    pub fn take_undo(&mut self) -> Option<HistoryEntry> {
+        self.undo.pop()
+    }
+
+    pub fn take_redo(&mut self) -> Option<HistoryEntry> {
+        self.redo.pop()
+    }
+
+    pub fn push_undo(&mut self, entry: HistoryEntry) {
+        self.undo.push(entry);
+        Self::trim(&mut self.undo, self.depth);
+    }
+
+    pub fn push_redo(&mut self, entry: HistoryEntry) {
+        self.redo.push(entry);
+        Self::trim(&mut self.redo, self.depth);
+    }
+
+    // Записи от самой старой к самой новой
+    pub fn undo_entries(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.undo.iter()
+    }
+
+    pub fn redo_entries(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.redo.iter()
+    }
+
+    // Строки файла: "depth N", "undo<TAB>команда<TAB>обратная..." и "redo<TAB>..."
+    pub fn to_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("depth {}", self.depth)];
+        for (kind, entries) in [("undo", &self.undo), ("redo", &self.redo)] {
+            for entry in entries {
+                let mut fields = vec![kind.to_string(), entry.command.clone()];
+                fields.extend(entry.inverse.iter().cloned());
+                lines.push(fields.join("\t"));
+            }
+        }
+        lines
+    }
+
+    pub fn load_line(&mut self, line: &str) {
+        if let Some(depth) = line.strip_prefix("depth ") {
+            if let Ok(depth) = depth.trim().parse::<usize>() {
+                self.set_depth(depth);
+            }
+            return;
+        }
+
+        let mut fields = line.split('\t');
+        let (kind, command) = match (fields.next(), fields.next()) {
+            (Some(kind), Some(command)) => (kind, command),
+            _ => return,
+        };
+        let entry = HistoryEntry {
+            command: command.to_string(),
+            inverse: fields.map(|field| field.to_string()).collect(),
+        };
+        match kind {
+            "undo" => self.push_undo(entry),
+            "redo" => self.push_redo(entry),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::History;
+
+    fn commands<'a>(entries: impl Iterator<Item = &'a super::HistoryEntry>) -> Vec<&'a str> {
+        entries.map(|entry| entry.command.as_str()).collect()
+    }
+
+    #[test]
+    fn record_clears_redo_and_skips_replayed_commands() {
+        let mut history = History::new(10);
+        history.record(" SPUSH\ta ", vec!["SPOP".to_string()]);
+        let entry = history.take_undo().unwrap();
+        assert_eq!(entry.command, "SPUSH a");
+        history.push_redo(entry);
+
+        history.set_replaying(true);
+        history.record("SPUSH b", vec![]);
+        history.set_replaying(false);
+        assert_eq!(history.undo_entries().count(), 0);
+        assert_eq!(history.redo_entries().count(), 1);
+
+        history.record("SPUSH c", vec![]);
+        assert_eq!(history.redo_entries().count(), 0);
+    }
+
+    #[test]
+    fn depth_drops_oldest_entries() {
+        let mut history = History::new(2);
+        for command in ["A", "B", "C"] {
+            history.record(command, vec![]);
+        }
+        assert_eq!(commands(history.undo_entries()), ["B", "C"]);
+        history.set_depth(1);
+        assert_eq!(commands(history.undo_entries()), ["C"]);
+        history.set_depth(0);
+        assert!(history.take_undo().is_none());
+    }
+
+    #[test]
+    fn lines_round_trip() {
+        let mut history = History::new(5);
+        history.record("SPUSH a", vec!["SPOP".to_string()]);
+        history.record("SPUSH b", vec!["_RESTORE stack a".to_string(), "SPOP".to_string()]);
+        let entry = history.take_undo().unwrap();
+        history.push_redo(entry);
+
+        let lines = history.to_lines();
+        assert_eq!(lines, ["depth 5", "undo\tSPUSH a\tSPOP", "redo\tSPUSH b\t_RESTORE stack a\tSPOP"]);
+
+        let mut loaded = History::new(100);
+        for line in lines.iter().map(String::as_str).chain(["bad", "depth x", "other\tX"]) {
+            loaded.load_line(line);
+        }
+        assert_eq!(loaded.depth(), 5);
+        assert_eq!(loaded.to_lines(), lines);
+    }
+}
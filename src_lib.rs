@@ -0,0 +1,31 @@
+// Структуры данных. Интерфейс командной строки (src_main.rs) подключает их
+// как библиотеку, поэтому их можно использовать и из других программ.
+
+#[path = "src_array.rs"]
+pub mod array;
+#[path = "src_singly_list.rs"]
+pub mod singly_list;
+#[path = "src_doubly_list.rs"]
+pub mod doubly_list;
+#[path = "src_stack.rs"]
+pub mod stack;
+#[path = "src_queue.rs"]
+pub mod queue;
+#[path = "src_full_binary_tree.rs"]
+pub mod full_binary_tree;
+#[path = "src_lru_cache.rs"]
+pub mod lru_cache;
+#[path = "src_deque.rs"]
+pub mod deque;
+#[path = "src_expression.rs"]
+pub mod expression;
+#[path = "src_balance.rs"]
+pub mod balance;
+#[path = "src_circular_queue.rs"]
+pub mod circular_queue;
+#[path = "src_priority_queue.rs"]
+pub mod priority_queue;
+#[path = "src_blocking_queue.rs"]
+pub mod blocking_queue;
+#[path = "src_two_stack_queue.rs"]
+pub mod two_stack_queue;
@@ -0,0 +1,575 @@
+use crate::doubly_list::DoublyList;
+use crate::output::{CommandResult, ErrorKind};
+use crate::registry::{restore_command, Call, CommandSpec, DataFile, DataStructures, Module};
+use crate::singly_list::SinglyList;
+
+// Команды односвязного и двусвязного списков.
+// FAPPEND и LAPPEND переносят за O(1) все элементы другого экземпляра того же списка,
+// FCOPY_L и LCOPY_F копируют элементы из списка другого типа.
+
+pub struct SinglyListModule {
+    singly_list: SinglyList,
+}
+
+impl SinglyListModule {
+    pub fn new() -> Self {
+        SinglyListModule {
+            singly_list: SinglyList::new(),
+        }
+    }
+
+    fn restore_command(&self) -> String {
+        restore_command("singly_list", self.singly_list.iter().cloned())
+    }
+
+    // Команда восстановления заданного экземпляра, а не выполняющего команду
+    fn restore_instance_command(&self, instance: &str) -> String {
+        restore_command(&format!("singly_list@{}", instance), self.singly_list.iter().cloned())
+    }
+}
+
+const SINGLY_LIST_COMMANDS: &[CommandSpec] = &[
+    CommandSpec { name: "FPUSH_HEAD", signature: "<value>", help: "Add a value to the head", lookup: false },
+    CommandSpec { name: "FPUSH_TAIL", signature: "<value>", help: "Add a value to the tail", lookup: false },
+    CommandSpec { name: "FDEL_HEAD", signature: "", help: "Remove the head", lookup: false },
+    CommandSpec { name: "FDEL_TAIL", signature: "", help: "Remove the tail", lookup: false },
+    CommandSpec { name: "FDEL_VALUE", signature: "<value>", help: "Remove the first occurrence of a value", lookup: false },
+    CommandSpec { name: "FDEL_ALL", signature: "<value>", help: "Remove all occurrences of a value", lookup: false },
+    CommandSpec { name: "FINSERT", signature: "<index:usize> <value>", help: "Insert a value at an index", lookup: false },
+    CommandSpec { name: "FDEL_INDEX", signature: "<index:usize>", help: "Remove the element at an index", lookup: false },
+    CommandSpec { name: "FAPPEND", signature: "<source>", help: "Move all elements of another singly list instance to the end", lookup: false },
+    CommandSpec { name: "FCOPY_L", signature: "", help: "Copy the doubly list to the end of the singly list", lookup: false },
+    CommandSpec { name: "FSEARCH", signature: "<value>", help: "Find the index of a value", lookup: true },
+    CommandSpec { name: "FLEN", signature: "", help: "Show the list length", lookup: true },
+    CommandSpec { name: "FPRINT", signature: "", help: "Print the list", lookup: true },
+];
+
+const SINGLY_LIST_FILES: &[DataFile] = &[DataFile {
+    suffix: "singly_list",
+    description: Some("singly list"),
+}];
+
+impl Module for SinglyListModule {
+    fn title(&self) -> &'static str {
+        "Singly List"
+    }
+
+    fn name(&self) -> &'static str {
+        "singly_list"
+    }
+
+    fn commands(&self) -> &'static [CommandSpec] {
+        SINGLY_LIST_COMMANDS
+    }
+
+    fn execute(&mut self, call: &Call, ds: &mut DataStructures, out: &mut CommandResult) {
+        let (query, parts) = (call.query, call.parts);
+        match call.command {
+            "FPUSH_HEAD" => {
+                self.singly_list.add_head(parts[1].to_string());
+                out.line(format!("Added \"{}\" to list head", parts[1]));
+                ds.record(query, vec!["FDEL_HEAD".to_string()]);
+            }
+            "FPUSH_TAIL" => {
+                self.singly_list.add_tail(parts[1].to_string());
+                out.line(format!("Added \"{}\" to list tail", parts[1]));
+                ds.record(query, vec!["FDEL_TAIL".to_string()]);
+            }
+            "FDEL_HEAD" => {
+                match self.singly_list.remove_head() {
+                    Ok(value) => {
+                        out.line(format!("Removed list head: \"{}\"", value));
+                        out.set_value(value.clone());
+                        ds.record(query, vec![format!("FPUSH_HEAD {}", value)]);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Empty, e),
+                }
+            }
+            "FDEL_TAIL" => {
+                match self.singly_list.remove_tail() {
+                    Ok(value) => {
+                        out.line(format!("Removed list tail: \"{}\"", value));
+                        out.set_value(value.clone());
+                        ds.record(query, vec![format!("FPUSH_TAIL {}", value)]);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Empty, e),
+                }
+            }
+            "FDEL_VALUE" => {
+                let index = self.singly_list.find(parts[1]);
+                match self.singly_list.remove_value(parts[1]) {
+                    Ok(_) => {
+                        out.line(format!("Removed value \"{}\" from list", parts[1]));
+                        if let Some(index) = index {
+                            ds.record(query, vec![format!("FINSERT {} {}", index, parts[1])]);
+                        }
+                    }
+                    Err(e) if self.singly_list.is_empty() => out.fail_as(ErrorKind::Empty, e),
+                    Err(e) => out.fail_as(ErrorKind::NotFound, e),
+                }
+            }
+            "FDEL_ALL" => {
+                let inverse: Vec<String> = self
+                    .singly_list
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, value)| value.as_str() == parts[1])
+                    .map(|(index, value)| format!("FINSERT {} {}", index, value))
+                    .collect();
+                let removed = self.singly_list.remove_all(parts[1]);
+                out.line(format!("Removed {} occurrence(s) of \"{}\" from list", removed, parts[1]));
+                out.set_value(removed);
+                if removed > 0 {
+                    ds.record(query, inverse);
+                }
+            }
+            "FAPPEND" => {
+                if parts[1] == call.instance {
+                    out.fail_as(ErrorKind::BadArguments, "Cannot append a list to itself");
+                    return;
+                }
+                let Some(source) = ds.instance_mut::<SinglyListModule>(parts[1]) else {
+                    out.fail_as(ErrorKind::NotFound, format!("No singly_list instance \"{}\"", parts[1]));
+                    return;
+                };
+                let inverse = vec![self.restore_command(), source.restore_instance_command(parts[1])];
+                let moved = source.singly_list.len();
+                self.singly_list.append(&mut source.singly_list);
+                out.line(format!("Moved {} element(s) from singly list \"{}\"", moved, parts[1]));
+                out.set_value(moved);
+                ds.record(query, inverse);
+            }
+            "FCOPY_L" => {
+                let Some(other) = ds.get_mut::<DoublyListModule>() else {
+                    out.fail_as(ErrorKind::Failed, "Doubly list is not available");
+                    return;
+                };
+                let copied = other.doubly_list.len();
+                let inverse = vec![self.restore_command()];
+                self.singly_list.extend(other.doubly_list.iter().cloned());
+                out.line(format!("Copied {} element(s) from doubly list to singly list", copied));
+                out.set_value(copied);
+                ds.record(query, inverse);
+            }
+            "FINSERT" => {
+                let index = call.number::<usize>(1);
+                match self.singly_list.insert(index, parts[2].to_string()) {
+                    Ok(_) => {
+                        out.line(format!("Inserted \"{}\" at list index {}", parts[2], index));
+                        ds.record(query, vec![format!("FDEL_INDEX {}", index)]);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::NotFound, e),
+                }
+            }
+            "FDEL_INDEX" => {
+                let index = call.number::<usize>(1);
+                match self.singly_list.remove(index) {
+                    Ok(value) => {
+                        out.line(format!("Removed \"{}\" at list index {}", value, index));
+                        out.set_value(value.clone());
+                        ds.record(query, vec![format!("FINSERT {} {}", index, value)]);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::NotFound, e),
+                }
+            }
+            "FSEARCH" => {
+                match self.singly_list.find(parts[1]) {
+                    Some(index) => {
+                        out.line(format!("Value \"{}\" found at index {}", parts[1], index));
+                        out.set_value(index);
+                    }
+                    None => {
+                        out.line(format!("Value \"{}\" not found", parts[1]));
+                        out.set_error(ErrorKind::NotFound, "Value not found");
+                    }
+                }
+            }
+            "FLEN" => {
+                out.line(format!("Singly list length: {}", self.singly_list.len()));
+                out.set_value(self.singly_list.len());
+            }
+            "FPRINT" => {
+                let values: Vec<&String> = self.singly_list.iter().collect();
+                out.line(format!("Singly List: {}nullptr", values.iter().map(|value| format!("{} -> ", value)).collect::<String>()));
+                out.set_values(values);
+            }
+            _ => unreachable!("{} is not a singly list command", call.command),
+        }
+    }
+
+    fn files(&self) -> &'static [DataFile] {
+        SINGLY_LIST_FILES
+    }
+
+    fn load(&mut self, _file: &str, lines: &[String]) -> Vec<String> {
+        self.singly_list
+            .extend(lines.iter().flat_map(|line| line.split_whitespace()).map(|value| value.to_string()));
+        Vec::new()
+    }
+
+    fn save(&self, _file: &str) -> String {
+        self.singly_list.iter().map(|value| format!("{} ", value)).collect()
+    }
+
+    fn snapshot(&self) -> Option<String> {
+        Some(self.restore_command())
+    }
+
+    fn restore(&mut self, _target: &str, values: &[&str]) -> Result<(), String> {
+        self.singly_list = values.iter().map(|value| value.to_string()).collect();
+        Ok(())
+    }
+}
+
+pub struct DoublyListModule {
+    doubly_list: DoublyList,
+}
+
+impl DoublyListModule {
+    pub fn new() -> Self {
+        DoublyListModule {
+            doubly_list: DoublyList::new(),
+        }
+    }
+
+    fn restore_command(&self) -> String {
+        restore_command("doubly_list", self.doubly_list.iter().cloned())
+    }
+
+    // Команда восстановления заданного экземпляра, а не выполняющего команду
+    fn restore_instance_command(&self, instance: &str) -> String {
+        restore_command(&format!("doubly_list@{}", instance), self.doubly_list.iter().cloned())
+    }
+}
+
+const DOUBLY_LIST_COMMANDS: &[CommandSpec] = &[
+    CommandSpec { name: "LPUSH_HEAD", signature: "<value>", help: "Add a value to the head", lookup: false },
+    CommandSpec { name: "LPUSH_TAIL", signature: "<value>", help: "Add a value to the tail", lookup: false },
+    CommandSpec { name: "LDEL_HEAD", signature: "", help: "Remove the head", lookup: false },
+    CommandSpec { name: "LDEL_TAIL", signature: "", help: "Remove the tail", lookup: false },
+    CommandSpec { name: "LDEL_VALUE", signature: "<value>", help: "Remove the first occurrence of a value", lookup: false },
+    CommandSpec { name: "LDEL_ALL", signature: "<value>", help: "Remove all occurrences of a value", lookup: false },
+    CommandSpec { name: "LINSERT", signature: "<index:usize> <value>", help: "Insert a value at an index", lookup: false },
+    CommandSpec { name: "LDEL_INDEX", signature: "<index:usize>", help: "Remove the element at an index", lookup: false },
+    CommandSpec { name: "LAPPEND", signature: "<source>", help: "Move all elements of another doubly list instance to the end", lookup: false },
+    CommandSpec { name: "LCOPY_F", signature: "", help: "Copy the singly list to the end of the doubly list", lookup: false },
+    CommandSpec { name: "LROTATE_LEFT", signature: "<steps:usize>", help: "Rotate the list left", lookup: false },
+    CommandSpec { name: "LROTATE_RIGHT", signature: "<steps:usize>", help: "Rotate the list right", lookup: false },
+    CommandSpec { name: "LREVERSE", signature: "", help: "Reverse the list", lookup: false },
+    CommandSpec { name: "LSWAP", signature: "<i:usize> <j:usize>", help: "Swap two elements", lookup: false },
+    CommandSpec { name: "LSEARCH", signature: "<value>", help: "Find the index of a value", lookup: true },
+    CommandSpec { name: "LLEN", signature: "", help: "Show the list length", lookup: true },
+    CommandSpec { name: "LPRINT_FORWARD", signature: "", help: "Print the list from head to tail", lookup: true },
+    CommandSpec { name: "LPRINT_BACKWARD", signature: "", help: "Print the list from tail to head", lookup: true },
+];
+
+const DOUBLY_LIST_FILES: &[DataFile] = &[DataFile {
+    suffix: "doubly_list",
+    description: Some("doubly list"),
+}];
+
+impl Module for DoublyListModule {
+    fn title(&self) -> &'static str {
+        "Doubly List"
+    }
+
+    fn name(&self) -> &'static str {
+        "doubly_list"
+    }
+
+    fn commands(&self) -> &'static [CommandSpec] {
+        DOUBLY_LIST_COMMANDS
+    }
+
+    fn execute(&mut self, call: &Call, ds: &mut DataStructures, out: &mut CommandResult) {
+        let (query, parts) = (call.query, call.parts);
+        match call.command {
+            "LPUSH_HEAD" => {
+                self.doubly_list.add_head(parts[1].to_string());
+                out.line(format!("Added \"{}\" to doubly list head", parts[1]));
+                ds.record(query, vec!["LDEL_HEAD".to_string()]);
+            }
+            "LPUSH_TAIL" => {
+                self.doubly_list.add_tail(parts[1].to_string());
+                out.line(format!("Added \"{}\" to doubly list tail", parts[1]));
+                ds.record(query, vec!["LDEL_TAIL".to_string()]);
+            }
+            "LDEL_HEAD" => {
+                match self.doubly_list.remove_head() {
+                    Ok(value) => {
+                        out.line(format!("Removed doubly list head: \"{}\"", value));
+                        out.set_value(value.clone());
+                        ds.record(query, vec![format!("LPUSH_HEAD {}", value)]);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Empty, e),
+                }
+            }
+            "LDEL_TAIL" => {
+                match self.doubly_list.remove_tail() {
+                    Ok(value) => {
+                        out.line(format!("Removed doubly list tail: \"{}\"", value));
+                        out.set_value(value.clone());
+                        ds.record(query, vec![format!("LPUSH_TAIL {}", value)]);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Empty, e),
+                }
+            }
+            "LDEL_VALUE" => {
+                let index = self.doubly_list.iter().position(|value| value == parts[1]);
+                match self.doubly_list.remove_value(parts[1]) {
+                    Ok(_) => {
+                        out.line(format!("Removed value \"{}\" from doubly list", parts[1]));
+                        if let Some(index) = index {
+                            ds.record(query, vec![format!("LINSERT {} {}", index, parts[1])]);
+                        }
+                    }
+                    Err(e) if self.doubly_list.is_empty() => out.fail_as(ErrorKind::Empty, e),
+                    Err(e) => out.fail_as(ErrorKind::NotFound, e),
+                }
+            }
+            "LDEL_ALL" => {
+                let inverse: Vec<String> = self
+                    .doubly_list
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, value)| value.as_str() == parts[1])
+                    .map(|(index, value)| format!("LINSERT {} {}", index, value))
+                    .collect();
+                let removed = self.doubly_list.remove_all(parts[1]);
+                out.line(format!("Removed {} occurrence(s) of \"{}\" from doubly list", removed, parts[1]));
+                out.set_value(removed);
+                if removed > 0 {
+                    ds.record(query, inverse);
+                }
+            }
+            "LAPPEND" => {
+                if parts[1] == call.instance {
+                    out.fail_as(ErrorKind::BadArguments, "Cannot append a list to itself");
+                    return;
+                }
+                let Some(source) = ds.instance_mut::<DoublyListModule>(parts[1]) else {
+                    out.fail_as(ErrorKind::NotFound, format!("No doubly_list instance \"{}\"", parts[1]));
+                    return;
+                };
+                let inverse = vec![self.restore_command(), source.restore_instance_command(parts[1])];
+                let moved = source.doubly_list.len();
+                self.doubly_list.append(&mut source.doubly_list);
+                out.line(format!("Moved {} element(s) from doubly list \"{}\"", moved, parts[1]));
+                out.set_value(moved);
+                ds.record(query, inverse);
+            }
+            "LCOPY_F" => {
+                let Some(other) = ds.get_mut::<SinglyListModule>() else {
+                    out.fail_as(ErrorKind::Failed, "Singly list is not available");
+                    return;
+                };
+                let copied = other.singly_list.len();
+                let inverse = vec![self.restore_command()];
+                self.doubly_list.extend(other.singly_list.iter().cloned());
+                out.line(format!("Copied {} element(s) from singly list to doubly list", copied));
+                out.set_value(copied);
+                ds.record(query, inverse);
+            }
+            "LROTATE_LEFT" => {
+                let n = call.number::<usize>(1);
+                self.doubly_list.rotate_left(n);
+                out.line(format!("Rotated doubly list left by {}", n));
+                ds.record(query, vec![format!("LROTATE_RIGHT {}", n)]);
+            }
+            "LROTATE_RIGHT" => {
+                let n = call.number::<usize>(1);
+                self.doubly_list.rotate_right(n);
+                out.line(format!("Rotated doubly list right by {}", n));
+                ds.record(query, vec![format!("LROTATE_LEFT {}", n)]);
+            }
+            "LREVERSE" => {
+                self.doubly_list.reverse();
+                out.line("Reversed doubly list");
+                ds.record(query, vec!["LREVERSE".to_string()]);
+            }
+            "LSWAP" => {
+                let (i, j) = (call.number::<usize>(1), call.number::<usize>(2));
+                match self.doubly_list.swap(i, j) {
+                    Ok(_) => {
+                        out.line(format!("Swapped doubly list elements {} and {}", i, j));
+                        ds.record(query, vec![format!("LSWAP {} {}", i, j)]);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::NotFound, e),
+                }
+            }
+            "LINSERT" => {
+                let index = call.number::<usize>(1);
+                match self.doubly_list.insert(index, parts[2].to_string()) {
+                    Ok(_) => {
+                        out.line(format!("Inserted \"{}\" at doubly list index {}", parts[2], index));
+                        ds.record(query, vec![format!("LDEL_INDEX {}", index)]);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::NotFound, e),
+                }
+            }
+            "LDEL_INDEX" => {
+                let index = call.number::<usize>(1);
+                match self.doubly_list.remove(index) {
+                    Ok(value) => {
+                        out.line(format!("Removed \"{}\" at doubly list index {}", value, index));
+                        out.set_value(value.clone());
+                        ds.record(query, vec![format!("LINSERT {} {}", index, value)]);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::NotFound, e),
+                }
+            }
+            "LSEARCH" => {
+                let found = self.doubly_list.find(parts[1]);
+                out.line(format!("Value \"{}\" {}", parts[1], if found { "found" } else { "not found" }));
+                out.set_value(found);
+                if !found {
+                    out.set_error(ErrorKind::NotFound, "Value not found");
+                }
+            }
+            "LLEN" => {
+                out.line(format!("Doubly list length: {}", self.doubly_list.len()));
+                out.set_value(self.doubly_list.len());
+            }
+            "LPRINT_FORWARD" => {
+                let values: Vec<&String> = self.doubly_list.iter().collect();
+                out.line(format!("Doubly List (forward): {}", values.iter().map(|value| format!("{} ", value)).collect::<String>()));
+                out.set_values(values);
+            }
+            "LPRINT_BACKWARD" => {
+                let values: Vec<&String> = self.doubly_list.iter().rev().collect();
+                out.line(format!("Doubly List (backward): {}", values.iter().map(|value| format!("{} ", value)).collect::<String>()));
+                out.set_values(values);
+            }
+            _ => unreachable!("{} is not a doubly list command", call.command),
+        }
+    }
+
+    fn files(&self) -> &'static [DataFile] {
+        DOUBLY_LIST_FILES
+    }
+
+    fn load(&mut self, _file: &str, lines: &[String]) -> Vec<String> {
+        self.doubly_list
+            .extend(lines.iter().flat_map(|line| line.split_whitespace()).map(|value| value.to_string()));
+        Vec::new()
+    }
+
+    fn save(&self, _file: &str) -> String {
+        self.doubly_list.iter().map(|value| format!("{} ", value)).collect()
+    }
+
+    fn snapshot(&self) -> Option<String> {
+        Some(self.restore_command())
+    }
+
+    fn restore(&mut self, _target: &str, values: &[&str]) -> Result<(), String> {
+        self.doubly_list = values.iter().map(|value| value.to_string()).collect();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::ErrorKind;
+    use crate::testing::{printed, run, structures};
+
+    #[test]
+    fn del_all_removes_every_occurrence_and_undoes() {
+        let mut ds = structures();
+        let out = run(&mut ds, &["FPUSH_TAIL x", "FPUSH_TAIL a", "FPUSH_TAIL x", "FDEL_ALL x"]);
+        assert_eq!(out.lines, ["Removed 2 occurrence(s) of \"x\" from list"]);
+        assert_eq!(printed(&mut ds, "FPRINT"), "Singly List: a -> nullptr");
+        run(&mut ds, &["UNDO"]);
+        assert_eq!(printed(&mut ds, "FPRINT"), "Singly List: x -> a -> x -> nullptr");
+
+        let out = run(&mut ds, &["LPUSH_TAIL y", "LPUSH_TAIL y", "LDEL_ALL y"]);
+        assert_eq!(out.exit_code(), 0);
+        assert_eq!(printed(&mut ds, "LLEN"), "Doubly list length: 0");
+        run(&mut ds, &["UNDO"]);
+        assert_eq!(printed(&mut ds, "LLEN"), "Doubly list length: 2");
+    }
+
+    #[test]
+    fn del_all_without_matches_is_not_recorded() {
+        let mut ds = structures();
+        let out = run(&mut ds, &["FPUSH_TAIL a", "FDEL_ALL x"]);
+        assert_eq!(out.exit_code(), 0);
+        run(&mut ds, &["UNDO"]);
+        assert_eq!(printed(&mut ds, "FLEN"), "Singly list length: 0");
+    }
+
+    #[test]
+    fn del_value_reports_missing_value() {
+        let mut ds = structures();
+        let out = run(&mut ds, &["LPUSH_TAIL a", "LDEL_VALUE b"]);
+        assert_eq!(out.kind, Some(ErrorKind::NotFound));
+        let out = run(&mut ds, &["LDEL_VALUE a"]);
+        assert_eq!(out.lines, ["Removed value \"a\" from doubly list"]);
+    }
+
+    #[test]
+    fn append_moves_instance_and_undoes() {
+        let mut ds = structures();
+        run(&mut ds, &["CREATE singly_list done", "FPUSH_TAIL a", "FPUSH_TAIL done b", "FPUSH_TAIL done c"]);
+        let out = run(&mut ds, &["FAPPEND done"]);
+        assert_eq!(out.lines, ["Moved 2 element(s) from singly list \"done\""]);
+        assert_eq!(printed(&mut ds, "FPRINT"), "Singly List: a -> b -> c -> nullptr");
+        assert_eq!(printed(&mut ds, "FPRINT done"), "Singly List: nullptr");
+        // Хвост после переноса указывает на последний элемент
+        run(&mut ds, &["FPUSH_TAIL d"]);
+        assert_eq!(printed(&mut ds, "FPRINT"), "Singly List: a -> b -> c -> d -> nullptr");
+
+        run(&mut ds, &["UNDO", "UNDO"]);
+        assert_eq!(printed(&mut ds, "FPRINT"), "Singly List: a -> nullptr");
+        assert_eq!(printed(&mut ds, "FPRINT done"), "Singly List: b -> c -> nullptr");
+    }
+
+    #[test]
+    fn append_into_named_instance_undoes_both() {
+        let mut ds = structures();
+        run(&mut ds, &["CREATE doubly_list todo", "LPUSH_TAIL todo a", "LPUSH_TAIL b"]);
+        let out = run(&mut ds, &["LAPPEND todo default"]);
+        assert_eq!(out.exit_code(), 0);
+        assert_eq!(printed(&mut ds, "LPRINT_BACKWARD todo"), "Doubly List (backward): b a ");
+        assert_eq!(printed(&mut ds, "LLEN"), "Doubly list length: 0");
+        run(&mut ds, &["UNDO"]);
+        assert_eq!(printed(&mut ds, "LLEN todo"), "Doubly list length: 1");
+        assert_eq!(printed(&mut ds, "LLEN"), "Doubly list length: 1");
+    }
+
+    #[test]
+    fn append_rejects_self_and_missing_source() {
+        let mut ds = structures();
+        run(&mut ds, &["FPUSH_TAIL a"]);
+        let out = run(&mut ds, &["FAPPEND default"]);
+        assert_eq!(out.kind, Some(ErrorKind::BadArguments));
+        let out = run(&mut ds, &["LAPPEND missing"]);
+        assert_eq!(out.kind, Some(ErrorKind::NotFound));
+        assert_eq!(out.error.as_deref(), Some("No doubly_list instance \"missing\""));
+        assert_eq!(printed(&mut ds, "FLEN"), "Singly list length: 1");
+    }
+
+    #[test]
+    fn copy_between_list_types_keeps_source() {
+        let mut ds = structures();
+        run(&mut ds, &["FPUSH_TAIL a", "LPUSH_TAIL x", "LPUSH_TAIL y"]);
+        let out = run(&mut ds, &["FCOPY_L"]);
+        assert_eq!(out.lines, ["Copied 2 element(s) from doubly list to singly list"]);
+        assert_eq!(printed(&mut ds, "FPRINT"), "Singly List: a -> x -> y -> nullptr");
+        assert_eq!(printed(&mut ds, "LLEN"), "Doubly list length: 2");
+        run(&mut ds, &["LCOPY_F"]);
+        assert_eq!(printed(&mut ds, "LLEN"), "Doubly list length: 5");
+        run(&mut ds, &["UNDO", "UNDO"]);
+        assert_eq!(printed(&mut ds, "FPRINT"), "Singly List: a -> nullptr");
+        assert_eq!(printed(&mut ds, "LLEN"), "Doubly list length: 2");
+    }
+
+    #[test]
+    fn len_commands_follow_changes() {
+        let mut ds = structures();
+        run(&mut ds, &["FPUSH_TAIL a", "FPUSH_HEAD b", "FINSERT 1 c", "FDEL_TAIL"]);
+        assert_eq!(printed(&mut ds, "FLEN"), "Singly list length: 2");
+        run(&mut ds, &["LPUSH_TAIL a", "LPUSH_TAIL b", "LDEL_HEAD", "LDEL_HEAD", "LDEL_HEAD"]);
+        assert_eq!(printed(&mut ds, "LLEN"), "Doubly list length: 0");
+    }
+}
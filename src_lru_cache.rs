@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+
+use crate::doubly_list::{DoublyList, NodeHandle};
+
+type EvictCallback = Box<dyn FnMut(&str, &str)>;
+
+// Дескрипторы в индексе выдаёт только order, и узлы никогда не переносятся
+const ORDER_IN_SYNC: &str = "LRU index out of sync with usage order";
+
+// LRU-кэш: двусвязный список ключей в порядке использования + хеш-индекс
+pub struct LruCache {
+    capacity: usize,
+    // Голова списка — самый недавно использованный ключ
+    order: DoublyList,
+    index: HashMap<String, (String, NodeHandle)>,
+    on_evict: Option<EvictCallback>,
+}
+
+impl LruCache {
+    pub fn new(capacity: usize) -> Self {
+        LruCache {
+            capacity,
+            order: DoublyList::new(),
+            index: HashMap::new(),
+            on_evict: None,
+        }
+    }
+
+    // Вызывается с ключом и значением каждого вытесненного элемента
+    pub fn set_on_evict<F: FnMut(&str, &str) + 'static>(&mut self, callback: F) {
+        self.on_evict = Some(Box::new(callback));
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // При уменьшении ёмкости лишние элементы вытесняются
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict_overflow();
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    // Очищает кэш без вызова обработчика вытеснения
+    pub fn clear(&mut self) {
+        self.order = DoublyList::new();
+        self.index.clear();
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.index.contains_key(key)
+    }
+
+    // Возвращает значение и помечает ключ как недавно использованный
+    pub fn get(&mut self, key: &str) -> Option<&String> {
+        let (value, node) = self.index.get(key)?;
+        self.order.move_node_to_head(node).expect(ORDER_IN_SYNC);
+        Some(value)
+    }
+
+    // Возвращает значение, не меняя порядок использования
+    pub fn peek(&self, key: &str) -> Option<&String> {
+        self.index.get(key).map(|(value, _)| value)
+    }
+
+    // Возвращает предыдущее значение ключа, если он уже был в кэше
+    pub fn put(&mut self, key: String, value: String) -> Option<String> {
+        if let Some((old_value, node)) = self.index.get_mut(&key) {
+            self.order.move_node_to_head(node).expect(ORDER_IN_SYNC);
+            return Some(std::mem::replace(old_value, value));
+        }
+
+        let node = self.order.push_head_node(key.clone());
+        self.index.insert(key, (value, node));
+        self.evict_overflow();
+        None
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        let (value, node) = self.index.remove(key)?;
+        self.order.remove_node(node).expect(ORDER_IN_SYNC);
+        Some(value)
+    }
+
+    // Пары (ключ, значение) от самого свежего к самому старому
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&String, &String)> {
+        self.order.iter().map(|key| (key, &self.index[key].0))
+    }
+
+    fn evict_overflow(&mut self) {
+        while self.index.len() > self.capacity {
+            let key = match self.order.cursor_back_mut().remove_current() {
+                Some(key) => key,
+                None => return,
+            };
+            if let Some((value, _)) = self.index.remove(&key) {
+                if let Some(callback) = self.on_evict.as_mut() {
+                    callback(&key, &value);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn keys(cache: &LruCache) -> Vec<&str> {
+        cache.iter().map(|(key, _)| key.as_str()).collect()
+    }
+
+    fn cache_with_log(capacity: usize) -> (LruCache, Rc<RefCell<Vec<String>>>) {
+        let mut cache = LruCache::new(capacity);
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&log);
+        cache.set_on_evict(move |key, value| sink.borrow_mut().push(format!("{}={}", key, value)));
+        (cache, log)
+    }
+
+    #[test]
+    fn put_evicts_least_recently_used() {
+        let (mut cache, log) = cache_with_log(2);
+        cache.put("a".to_string(), "1".to_string());
+        cache.put("b".to_string(), "2".to_string());
+        cache.put("c".to_string(), "3".to_string());
+        assert_eq!(*log.borrow(), ["a=1"]);
+        assert_eq!(keys(&cache), ["c", "b"]);
+        assert!(!cache.contains("a"));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn get_and_update_refresh_recency() {
+        let (mut cache, log) = cache_with_log(2);
+        cache.put("a".to_string(), "1".to_string());
+        cache.put("b".to_string(), "2".to_string());
+        assert_eq!(cache.get("a"), Some(&"1".to_string()));
+        cache.put("c".to_string(), "3".to_string());
+        assert_eq!(*log.borrow(), ["b=2"]);
+
+        assert_eq!(cache.put("a".to_string(), "10".to_string()), Some("1".to_string()));
+        cache.put("d".to_string(), "4".to_string());
+        assert_eq!(*log.borrow(), ["b=2", "c=3"]);
+        assert_eq!(keys(&cache), ["d", "a"]);
+        assert_eq!(cache.peek("a"), Some(&"10".to_string()));
+    }
+
+    #[test]
+    fn peek_does_not_refresh_recency() {
+        let mut cache = LruCache::new(2);
+        cache.put("a".to_string(), "1".to_string());
+        cache.put("b".to_string(), "2".to_string());
+        assert_eq!(cache.peek("a"), Some(&"1".to_string()));
+        cache.put("c".to_string(), "3".to_string());
+        assert_eq!(cache.peek("a"), None);
+        assert_eq!(cache.get("missing"), None);
+    }
+
+    #[test]
+    fn shrinking_capacity_evicts_oldest() {
+        let (mut cache, log) = cache_with_log(3);
+        for (key, value) in [("a", "1"), ("b", "2"), ("c", "3")] {
+            cache.put(key.to_string(), value.to_string());
+        }
+        cache.set_capacity(1);
+        assert_eq!(*log.borrow(), ["a=1", "b=2"]);
+        assert_eq!(keys(&cache), ["c"]);
+        cache.set_capacity(0);
+        assert!(cache.is_empty());
+        cache.put("d".to_string(), "4".to_string());
+        assert!(cache.is_empty());
+        assert_eq!(log.borrow().last().map(String::as_str), Some("d=4"));
+    }
+
+    #[test]
+    fn remove_and_clear_skip_eviction_callback() {
+        let (mut cache, log) = cache_with_log(2);
+        cache.put("a".to_string(), "1".to_string());
+        cache.put("b".to_string(), "2".to_string());
+        assert_eq!(cache.remove("a"), Some("1".to_string()));
+        assert_eq!(cache.remove("a"), None);
+        cache.put("c".to_string(), "3".to_string());
+        assert!(log.borrow().is_empty());
+        cache.clear();
+        assert!(cache.is_empty());
+        cache.put("d".to_string(), "4".to_string());
+        assert_eq!(keys(&cache), ["d"]);
+        assert!(log.borrow().is_empty());
+    }
+}
@@ -1,492 +1,919 @@
 use std::env;
-use std::fs::{File, OpenOptions};
-use std::io::{BufRead, BufReader, Write};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
 use std::path::Path;
+use std::process;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Структуры данных подключаются из библиотеки; пути crate::<модуль> в модулях команд
+// ведут к ним через этот импорт
+use lab_data_structures::{
+    array, balance, circular_queue, deque, doubly_list, expression, full_binary_tree, lru_cache,
+    priority_queue, queue, singly_list, stack, two_stack_queue,
+};
+
+#[path = "src_history.rs"]
+mod history;
+#[path = "src_cli.rs"]
+mod cli;
+#[path = "src_output.rs"]
+mod output;
+#[path = "src_args.rs"]
+mod args;
+#[path = "src_registry.rs"]
+mod registry;
+#[path = "src_transaction.rs"]
+mod transaction;
+#[path = "src_array_commands.rs"]
+mod array_commands;
+#[path = "src_list_commands.rs"]
+mod list_commands;
+#[path = "src_stack_commands.rs"]
+mod stack_commands;
+#[path = "src_expression_commands.rs"]
+mod expression_commands;
+#[path = "src_queue_commands.rs"]
+mod queue_commands;
+#[path = "src_tree_commands.rs"]
+mod tree_commands;
+#[path = "src_cache_commands.rs"]
+mod cache_commands;
+#[path = "src_deque_commands.rs"]
+mod deque_commands;
+
+use history::History;
+use cli::{Action, Mode, OutputFormat};
+use output::{CommandResult, ErrorKind, Status};
+use registry::{Call, CommandSpec, DataStructures, Factory, DEFAULT_INSTANCE};
+use transaction::Transaction;
+use array_commands::ArrayModule;
+use list_commands::{DoublyListModule, SinglyListModule};
+use stack_commands::StackModule;
+use expression_commands::ExpressionModule;
+use queue_commands::{PriorityQueueModule, QueueBackend, QueueModule};
+use tree_commands::TreeModule;
+use cache_commands::CacheModule;
+use deque_commands::DequeModule;
+
+// Модули структур данных в порядке справки и загрузки файлов.
+// Новая структура подключается добавлением строки в этот список;
+// функция создаёт каждый экземпляр структуры, начиная с экземпляра по умолчанию.
+fn create_data_structures(queue_backend: QueueBackend) -> DataStructures {
+    let notices = Rc::new(RefCell::new(Vec::new()));
+    let cache_notices = Rc::clone(&notices);
+    let factories: Vec<Factory> = vec![
+        Box::new(|| Box::new(ArrayModule::new())),
+        Box::new(|| Box::new(SinglyListModule::new())),
+        Box::new(|| Box::new(DoublyListModule::new())),
+        Box::new(|| Box::new(StackModule::new())),
+        Box::new(|| Box::new(ExpressionModule)),
+        Box::new(move || Box::new(QueueModule::new(queue_backend))),
+        Box::new(|| Box::new(PriorityQueueModule::new())),
+        Box::new(|| Box::new(TreeModule::new())),
+        Box::new(move || Box::new(CacheModule::new(10, Rc::clone(&cache_notices)))),
+        Box::new(|| Box::new(DequeModule::new())),
+    ];
+    DataStructures::new(factories, History::new(20), notices)
+}
 
-mod array;
-mod singly_list;
-mod doubly_list;
-mod stack;
-mod queue;
-mod full_binary_tree;
-
-use array::Array;
-use singly_list::SinglyList;
-use doubly_list::DoublyList;
-use stack::Stack;
-use queue::Queue;
-use full_binary_tree::FullBinaryTree;
-
-// Глобальные структуры данных
-struct DataStructures {
-    array: Array,
-    singly_list: SinglyList,
-    doubly_list: DoublyList,
-    stack: Stack,
-    queue: Queue,
-    tree: FullBinaryTree,
+// Файл данных экземпляра: {base}_{suffix}.txt для экземпляра по умолчанию,
+// {base}_{suffix}.{name}.txt для именованного
+fn data_file_path(base_filename: &str, suffix: &str, instance: &str) -> String {
+    if instance == DEFAULT_INSTANCE {
+        format!("{}_{}.txt", base_filename, suffix)
+    } else {
+        format!("{}_{}.{}.txt", base_filename, suffix, instance)
+    }
 }
 
-impl DataStructures {
-    fn new() -> Self {
-        DataStructures {
-            array: Array::new(10),
-            singly_list: SinglyList::new(),
-            doubly_list: DoublyList::new(),
-            stack: Stack::new(10),
-            queue: Queue::new(),
-            tree: FullBinaryTree::new(),
-        }
+// Название экземпляра в сообщениях о загрузке и сохранении
+fn instance_description(description: &str, instance: &str) -> String {
+    if instance == DEFAULT_INSTANCE {
+        description.to_string()
+    } else {
+        format!("{} \"{}\"", description, instance)
     }
 }
 
 // Загрузка данных из файлов
-fn load_from_files(base_filename: &str, ds: &mut DataStructures) {
-    // Загрузка массива
-    let array_file = format!("{}_array.txt", base_filename);
-    if Path::new(&array_file).exists() {
-        if let Ok(file) = File::open(&array_file) {
-            let reader = BufReader::new(file);
-            for line in reader.lines() {
-                if let Ok(line) = line {
-                    for value in line.split_whitespace() {
-                        ds.array.add_back(value.to_string());
-                    }
-                }
-            }
-            println!("Loaded array from {}", array_file);
-        }
-    }
+// Ошибки открытия файлов собираются, чтобы сообщить обо всех сразу
+fn open_file(path: &str, errors: &mut Vec<String>) -> Option<File> {
+    File::open(path)
+        .map_err(|e| errors.push(format!("Cannot read {}: {}", path, e)))
+        .ok()
+}
 
-    // Загрузка односвязного списка
-    let slist_file = format!("{}_singly_list.txt", base_filename);
-    if Path::new(&slist_file).exists() {
-        if let Ok(file) = File::open(&slist_file) {
-            let reader = BufReader::new(file);
-            for line in reader.lines() {
-                if let Ok(line) = line {
-                    for value in line.split_whitespace() {
-                        ds.singly_list.add_tail(value.to_string());
-                    }
-                }
+// Строки файла до первой ошибки чтения; ошибка добавляется в errors
+fn read_lines(file: File, path: &str, errors: &mut Vec<String>) -> Vec<String> {
+    let mut lines = Vec::new();
+    for line in BufReader::new(file).lines() {
+        match line {
+            Ok(line) => lines.push(line),
+            Err(e) => {
+                errors.push(format!("Cannot read {}: {}", path, e));
+                break;
             }
-            println!("Loaded singly list from {}", slist_file);
         }
     }
+    lines
+}
 
-    // Загрузка двусвязного списка
-    let dlist_file = format!("{}_doubly_list.txt", base_filename);
-    if Path::new(&dlist_file).exists() {
-        if let Ok(file) = File::open(&dlist_file) {
-            let reader = BufReader::new(file);
-            for line in reader.lines() {
-                if let Ok(line) = line {
-                    for value in line.split_whitespace() {
-                        ds.doubly_list.add_tail(value.to_string());
+fn create_file(path: &str, errors: &mut Vec<String>) -> Option<File> {
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
+        .map_err(|e| errors.push(format!("Cannot write {}: {}", path, e)))
+        .ok()
+}
+
+fn load_from_files(base_filename: &str, ds: &mut DataStructures, quiet: bool) -> Result<(), String> {
+    let mut errors = Vec::new();
+
+    // Именованные экземпляры: строки "структура имя"
+    let instances_file = format!("{}_instances.txt", base_filename);
+    if Path::new(&instances_file).exists() {
+        if let Some(file) = open_file(&instances_file, &mut errors) {
+            for line in read_lines(file, &instances_file, &mut errors) {
+                if let [structure, name] = line.split_whitespace().collect::<Vec<_>>().as_slice() {
+                    if let Err(e) = ds.create_instance(structure, name) {
+                        eprintln!("Error: {}", e);
                     }
                 }
             }
-            println!("Loaded doubly list from {}", dlist_file);
+            if !quiet {
+                println!("Loaded instances from {}", instances_file);
+            }
         }
     }
 
-    // Загрузка стека
-    let stack_file = format!("{}_stack.txt", base_filename);
-    if Path::new(&stack_file).exists() {
-        if let Ok(file) = File::open(&stack_file) {
-            let reader = BufReader::new(file);
-            for line in reader.lines() {
-                if let Ok(line) = line {
-                    for value in line.split_whitespace() {
-                        ds.stack.push(value.to_string());
-                    }
+    // Каждая структура разбирает свои файлы сама
+    for (instance, module) in ds.instances_mut() {
+        for data_file in module.files() {
+            let path = data_file_path(base_filename, data_file.suffix, instance);
+            if !Path::new(&path).exists() {
+                continue;
+            }
+            if let Some(file) = open_file(&path, &mut errors) {
+                let lines = read_lines(file, &path, &mut errors);
+                for e in module.load(data_file.suffix, &lines) {
+                    eprintln!("Error: {}", e);
+                }
+                if let (Some(description), false) = (data_file.description, quiet) {
+                    println!("Loaded {} from {}", instance_description(description, instance), path);
                 }
             }
-            println!("Loaded stack from {}", stack_file);
         }
     }
 
-    // Загрузка очереди
-    let queue_file = format!("{}_queue.txt", base_filename);
-    if Path::new(&queue_file).exists() {
-        if let Ok(file) = File::open(&queue_file) {
-            let reader = BufReader::new(file);
-            for line in reader.lines() {
-                if let Ok(line) = line {
-                    for value in line.split_whitespace() {
-                        ds.queue.enqueue(value.to_string());
-                    }
-                }
+    // Загрузка истории изменений
+    let history_file = format!("{}_history.txt", base_filename);
+    if Path::new(&history_file).exists() {
+        if let Some(file) = open_file(&history_file, &mut errors) {
+            for line in read_lines(file, &history_file, &mut errors) {
+                ds.history.load_line(&line);
+            }
+            if !quiet {
+                println!("Loaded history from {}", history_file);
             }
-            println!("Loaded queue from {}", queue_file);
         }
     }
 
-    // Загрузка дерева
-    let tree_file = format!("{}_tree.txt", base_filename);
-    if Path::new(&tree_file).exists() {
-        if let Ok(file) = File::open(&tree_file) {
-            let reader = BufReader::new(file);
-            for line in reader.lines() {
-                if let Ok(line) = line {
-                    for value in line.split_whitespace() {
-                        ds.tree.insert(value.to_string());
-                    }
-                }
-            }
-            println!("Loaded tree from {}", tree_file);
-        }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
     }
 }
 
 // Сохранение данных в файлы
-fn save_to_files(base_filename: &str, ds: &DataStructures) {
-    // Сохранение массива
-    let array_file = format!("{}_array.txt", base_filename);
-    if let Ok(mut file) = OpenOptions::new().write(true).create(true).truncate(true).open(&array_file) {
-        for value in ds.array.iter() {
-            write!(file, "{} ", value).ok();
+fn save_to_files(base_filename: &str, ds: &DataStructures, quiet: bool) -> Result<(), String> {
+    let mut errors = Vec::new();
+
+    for (instance, module) in ds.instances() {
+        for data_file in module.files() {
+            let path = data_file_path(base_filename, data_file.suffix, instance);
+            if let Some(mut file) = create_file(&path, &mut errors) {
+                write!(file, "{}", module.save(data_file.suffix)).ok();
+                if let (Some(description), false) = (data_file.description, quiet) {
+                    println!("Saved {} to {}", instance_description(description, instance), path);
+                }
+            }
         }
-        println!("Saved array to {}", array_file);
     }
 
-    // Сохранение односвязного списка
-    let slist_file = format!("{}_singly_list.txt", base_filename);
-    if let Ok(mut file) = OpenOptions::new().write(true).create(true).truncate(true).open(&slist_file) {
-        for value in ds.singly_list.iter() {
-            write!(file, "{} ", value).ok();
+    // Файлы удалённых и переименованных экземпляров
+    for (instance, module) in ds.removed_instances() {
+        for data_file in module.files() {
+            let path = data_file_path(base_filename, data_file.suffix, instance);
+            match fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => errors.push(format!("Cannot remove {}: {}", path, e)),
+            }
         }
-        println!("Saved singly list to {}", slist_file);
     }
 
-    // Сохранение двусвязного списка
-    let dlist_file = format!("{}_doubly_list.txt", base_filename);
-    if let Ok(mut file) = OpenOptions::new().write(true).create(true).truncate(true).open(&dlist_file) {
-        for value in ds.doubly_list.iter() {
-            write!(file, "{} ", value).ok();
+    // Список именованных экземпляров; без них файл не нужен
+    let instances_file = format!("{}_instances.txt", base_filename);
+    let named: Vec<String> = ds
+        .instance_names()
+        .flat_map(|(structure, names)| {
+            names
+                .into_iter()
+                .filter(|&name| name != DEFAULT_INSTANCE)
+                .map(move |name| format!("{} {}", structure, name))
+        })
+        .collect();
+    if named.is_empty() {
+        if Path::new(&instances_file).exists() {
+            if let Err(e) = fs::remove_file(&instances_file) {
+                errors.push(format!("Cannot remove {}: {}", instances_file, e));
+            }
+        }
+    } else if let Some(mut file) = create_file(&instances_file, &mut errors) {
+        for line in &named {
+            writeln!(file, "{}", line).ok();
+        }
+        if !quiet {
+            println!("Saved instances to {}", instances_file);
         }
-        println!("Saved doubly list to {}", dlist_file);
     }
 
-    // Сохранение стека
-    let stack_file = format!("{}_stack.txt", base_filename);
-    if let Ok(mut file) = OpenOptions::new().write(true).create(true).truncate(true).open(&stack_file) {
-        let values: Vec<&String> = ds.stack.iter().collect();
-        for value in values.iter().rev() {
-            write!(file, "{} ", value).ok();
+    // Сохранение истории изменений
+    let history_file = format!("{}_history.txt", base_filename);
+    if let Some(mut file) = create_file(&history_file, &mut errors) {
+        for line in ds.history.to_lines() {
+            writeln!(file, "{}", line).ok();
+        }
+        if !quiet {
+            println!("Saved history to {}", history_file);
         }
-        println!("Saved stack to {}", stack_file);
     }
 
-    // Сохранение очереди
-    let queue_file = format!("{}_queue.txt", base_filename);
-    if let Ok(mut file) = OpenOptions::new().write(true).create(true).truncate(true).open(&queue_file) {
-        for value in ds.queue.iter() {
-            write!(file, "{} ", value).ok();
-        }
-        println!("Saved queue to {}", queue_file);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
     }
+}
 
-    // Сохранение дерева
-    let tree_file = format!("{}_tree.txt", base_filename);
-    if let Ok(mut file) = OpenOptions::new().write(true).create(true).truncate(true).open(&tree_file) {
-        for value in ds.tree.collect_inorder() {
-            write!(file, "{} ", value).ok();
-        }
-        println!("Saved tree to {}", tree_file);
+// Команды, не относящиеся к отдельной структуре
+const HISTORY_COMMANDS: &[CommandSpec] = &[
+    CommandSpec { name: "UNDO", signature: "", help: "Undo the last change", lookup: false },
+    CommandSpec { name: "REDO", signature: "", help: "Redo the last undone change", lookup: false },
+    CommandSpec { name: "HISTORY", signature: "", help: "Show the undo and redo history", lookup: true },
+    CommandSpec { name: "HISTORY_DEPTH", signature: "[depth:usize]", help: "Show or set the history depth", lookup: false },
+];
+
+const INSTANCE_COMMANDS: &[CommandSpec] = &[
+    CommandSpec { name: "CREATE", signature: "<structure> <name>", help: "Create a named instance of a structure", lookup: false },
+    CommandSpec { name: "DROP", signature: "<structure> <name>", help: "Remove a named instance", lookup: false },
+    CommandSpec { name: "RENAME", signature: "<structure> <name> <new_name>", help: "Rename a named instance", lookup: false },
+    CommandSpec { name: "INSTANCES", signature: "[structure]", help: "List the instances of each structure", lookup: true },
+];
+
+const TRANSACTION_COMMANDS: &[CommandSpec] = &[
+    CommandSpec { name: "BEGIN", signature: "", help: "Start a group of commands applied together", lookup: false },
+    CommandSpec { name: "COMMIT", signature: "", help: "Keep the changes made since BEGIN", lookup: false },
+    CommandSpec { name: "ROLLBACK", signature: "", help: "Discard the changes made since BEGIN", lookup: false },
+];
+
+const OTHER_COMMANDS: &[CommandSpec] = &[
+    CommandSpec { name: "HELP", signature: "[command]", help: "List the commands or describe one command", lookup: true },
+];
+
+fn find_command(ds: &DataStructures, command: &str) -> Option<&'static CommandSpec> {
+    HISTORY_COMMANDS
+        .iter()
+        .chain(INSTANCE_COMMANDS)
+        .chain(TRANSACTION_COMMANDS)
+        .chain(OTHER_COMMANDS)
+        .find(|spec| spec.name == command)
+        .or_else(|| ds.find_command(command))
+}
+
+// Список команд по группам; внутренние команды, начинающиеся с "_", скрыты
+fn list_commands(ds: &DataStructures, out: &mut CommandResult) {
+    let names = |commands: &[CommandSpec]| {
+        commands
+            .iter()
+            .map(|spec| spec.name)
+            .filter(|name| !name.starts_with('_'))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    out.line("Available commands:");
+    for module in ds.modules() {
+        out.line(format!("{}: {}", module.title(), names(module.commands())));
     }
+    out.line(format!("History: {}", names(HISTORY_COMMANDS)));
+    out.line(format!("Instances: {}", names(INSTANCE_COMMANDS)));
+    out.line(format!("Transactions: {}", names(TRANSACTION_COMMANDS)));
+    out.line(format!("Other: {}", names(OTHER_COMMANDS)));
 }
 
-// Обработка команд
-fn process_command(query: &str, ds: &mut DataStructures) {
-    let parts: Vec<&str> = query.split_whitespace().collect();
+// Обработка команд: история, экземпляры, транзакции и справка обрабатываются здесь,
+// остальные команды передаются модулю структуры
+fn process_command(query: &str, ds: &mut DataStructures) -> CommandResult {
+    let mut out = CommandResult::new(query);
+    let mut parts: Vec<&str> = query.split_whitespace().collect();
     if parts.is_empty() {
-        return;
+        return out;
     }
 
     let command = parts[0].to_uppercase();
 
+    let Some(spec) = find_command(ds, &command) else {
+        out.line(format!("Unknown command: {}", command));
+        list_commands(ds, &mut out);
+        out.set_error(ErrorKind::UnknownCommand, format!("Unknown command: {}", command));
+        return out;
+    };
+    // Имя экземпляра убирается из аргументов, модуль получает обычную команду
+    let instance = match ds.select_instance(spec, &parts[1..]) {
+        Some(instance) => {
+            parts.remove(1);
+            instance
+        }
+        None => DEFAULT_INSTANCE,
+    };
+    if let Err(e) = args::validate(&command, spec.signature, &parts[1..]) {
+        out.fail_as(ErrorKind::BadArguments, e);
+        return out;
+    }
+    let call = Call {
+        query,
+        command: &command,
+        parts: &parts,
+        instance,
+    };
+
     match command.as_str() {
-        // Команды для массива
-        "MPUSH_BACK" => {
-            if parts.len() > 1 {
-                ds.array.add_back(parts[1].to_string());
-                println!("Added \"{}\" to array", parts[1]);
-            }
-        }
-        "MPUSH_INDEX" => {
-            if parts.len() > 2 {
-                if let Ok(index) = parts[1].parse::<usize>() {
-                    match ds.array.add_index(index, parts[2].to_string()) {
-                        Ok(_) => println!("Added \"{}\" at index {}", parts[2], index),
-                        Err(e) => println!("Error: {}", e),
-                    }
+        // История изменений
+        "UNDO" => match ds.history.take_undo() {
+            Some(entry) => {
+                if replay(&entry.inverse, ds, &mut out) {
+                    out.line(format!("Undone: {}", entry.command));
+                    ds.history.push_redo(entry);
+                } else {
+                    out.line(format!("Cannot undo: {}", entry.command));
+                    ds.history.push_undo(entry);
                 }
             }
-        }
-        "MDEL" => {
-            if parts.len() > 1 {
-                if let Ok(index) = parts[1].parse::<usize>() {
-                    match ds.array.remove_index(index) {
-                        Ok(_) => println!("Removed element at index {}", index),
-                        Err(e) => println!("Error: {}", e),
-                    }
-                }
-            }
-        }
-        "MGET" => {
-            if parts.len() > 1 {
-                if let Ok(index) = parts[1].parse::<usize>() {
-                    match ds.array.get_index(index) {
-                        Ok(value) => println!("Array[{}] = \"{}\"", index, value),
-                        Err(e) => println!("Error: {}", e),
-                    }
+            None => out.fail_as(ErrorKind::Empty, "Nothing to undo"),
+        },
+        "REDO" => match ds.history.take_redo() {
+            Some(entry) => {
+                if replay(std::slice::from_ref(&entry.command), ds, &mut out) {
+                    out.line(format!("Redone: {}", entry.command));
+                    ds.history.push_undo(entry);
+                } else {
+                    out.line(format!("Cannot redo: {}", entry.command));
+                    ds.history.push_redo(entry);
                 }
             }
-        }
-        "MREPLACE" => {
-            if parts.len() > 2 {
-                if let Ok(index) = parts[1].parse::<usize>() {
-                    match ds.array.replace_index(index, parts[2].to_string()) {
-                        Ok(_) => println!("Replaced array[{}] with \"{}\"", index, parts[2]),
-                        Err(e) => println!("Error: {}", e),
-                    }
-                }
+            None => out.fail_as(ErrorKind::Empty, "Nothing to redo"),
+        },
+        "HISTORY" => {
+            out.line("Undo (oldest first):");
+            for entry in ds.history.undo_entries() {
+                out.line(format!("  {}", entry.command));
             }
-        }
-        "MLEN" => {
-            println!("Array length: {}", ds.array.len());
-        }
-        "MPRINT" => {
-            print!("Array: ");
-            ds.array.print();
-        }
-
-        // Команды для односвязного списка
-        "FPUSH_HEAD" => {
-            if parts.len() > 1 {
-                ds.singly_list.add_head(parts[1].to_string());
-                println!("Added \"{}\" to list head", parts[1]);
+            out.line("Redo (oldest first):");
+            for entry in ds.history.redo_entries() {
+                out.line(format!("  {}", entry.command));
             }
+            out.set_values(ds.history.undo_entries().map(|entry| &entry.command));
         }
-        "FPUSH_TAIL" => {
+        "HISTORY_DEPTH" => {
             if parts.len() > 1 {
-                ds.singly_list.add_tail(parts[1].to_string());
-                println!("Added \"{}\" to list tail", parts[1]);
+                let depth = call.number::<usize>(1);
+                ds.history.set_depth(depth);
+                out.line(format!("History depth set to {}", depth));
+            } else {
+                out.line(format!("History depth: {}", ds.history.depth()));
+                out.set_value(ds.history.depth());
+            }
+        }
+        // Именованные экземпляры
+        "CREATE" => match ds.create_instance(parts[1], parts[2]) {
+            Ok(()) => {
+                out.line(format!("Created {} \"{}\"", parts[1].to_lowercase(), parts[2]));
+                ds.history.record(query, vec![format!("DROP {} {}", parts[1], parts[2])]);
+            }
+            Err(e) => out.fail_as(ErrorKind::Failed, e),
+        },
+        "DROP" => match ds.drop_instance(parts[1], parts[2]) {
+            Ok(snapshot) => {
+                out.line(format!("Dropped {} \"{}\"", parts[1].to_lowercase(), parts[2]));
+                // Без снимка содержимое не вернуть, поэтому удаление не отменяется
+                if let Some(snapshot) = snapshot {
+                    let inverse = vec![format!("CREATE {} {}", parts[1], parts[2]), snapshot];
+                    ds.history.record(query, inverse);
+                }
             }
-        }
-        "FDEL_HEAD" => {
-            match ds.singly_list.remove_head() {
-                Ok(_) => println!("Removed list head"),
-                Err(e) => println!("Error: {}", e),
+            Err(e) => out.fail_as(ErrorKind::Failed, e),
+        },
+        "RENAME" => match ds.rename_instance(parts[1], parts[2], parts[3]) {
+            Ok(()) => {
+                out.line(format!("Renamed {} \"{}\" to \"{}\"", parts[1].to_lowercase(), parts[2], parts[3]));
+                ds.history.record(query, vec![format!("RENAME {} {} {}", parts[1], parts[3], parts[2])]);
+            }
+            Err(e) => out.fail_as(ErrorKind::Failed, e),
+        },
+        "INSTANCES" => {
+            let mut values = Vec::new();
+            let mut found = false;
+            for (structure, names) in ds.instance_names() {
+                if parts.len() > 1 && !structure.eq_ignore_ascii_case(parts[1]) {
+                    continue;
+                }
+                found = true;
+                out.line(format!("{}: {}", structure, names.join(", ")));
+                values.extend(names.iter().map(|name| format!("{} {}", structure, name)));
             }
-        }
-        "FDEL_TAIL" => {
-            match ds.singly_list.remove_tail() {
-                Ok(_) => println!("Removed list tail"),
-                Err(e) => println!("Error: {}", e),
+            if found {
+                out.set_values(values);
+            } else {
+                out.fail_as(ErrorKind::NotFound, format!("Unknown structure \"{}\"", parts[1]));
             }
         }
-        "FDEL_VALUE" => {
-            if parts.len() > 1 {
-                match ds.singly_list.remove_value(parts[1]) {
-                    Ok(_) => println!("Removed value \"{}\" from list", parts[1]),
-                    Err(e) => println!("Error: {}", e),
+
+        // Транзакции
+        "BEGIN" => match &ds.transaction {
+            Some(_) => out.fail_as(ErrorKind::Failed, "Transaction already started"),
+            None => match ds.snapshot() {
+                Ok(snapshot) => {
+                    ds.transaction = Some(Transaction::Active(snapshot));
+                    out.line("Transaction started");
                 }
-            }
-        }
-        "FSEARCH" => {
-            if parts.len() > 1 {
-                match ds.singly_list.find(parts[1]) {
-                    Some(index) => println!("Value \"{}\" found at index {}", parts[1], index),
-                    None => println!("Value \"{}\" not found", parts[1]),
+                Err(e) => out.fail_as(ErrorKind::Failed, e),
+            },
+        },
+        "COMMIT" => match ds.transaction.take() {
+            Some(Transaction::Active(_)) => out.line("Transaction committed"),
+            Some(Transaction::Aborted) => out.fail_as(ErrorKind::Failed, "Transaction was aborted, changes rolled back"),
+            None => out.fail_as(ErrorKind::Failed, "No transaction in progress"),
+        },
+        "ROLLBACK" => match ds.transaction.take() {
+            Some(Transaction::Active(snapshot)) => match ds.rollback(snapshot) {
+                Ok(()) => out.line("Transaction rolled back"),
+                Err(e) => out.fail_as(ErrorKind::Failed, e),
+            },
+            Some(Transaction::Aborted) => out.line("Transaction rolled back"),
+            None => out.fail_as(ErrorKind::Failed, "No transaction in progress"),
+        },
+
+        // Справка
+        "HELP" => match parts.get(1) {
+            Some(name) => match find_command(ds, &name.to_uppercase()) {
+                Some(spec) => {
+                    let usage = if spec.signature.is_empty() {
+                        spec.name.to_string()
+                    } else {
+                        format!("{} {}", spec.name, spec.signature)
+                    };
+                    out.line(format!("{} - {}", usage, spec.help));
+                    out.set_value(usage);
                 }
-            }
-        }
-        "FPRINT" => {
-            print!("Singly List: ");
-            ds.singly_list.print();
-        }
+                None => out.fail_as(ErrorKind::UnknownCommand, format!("Unknown command: {}", name.to_uppercase())),
+            },
+            None => list_commands(ds, &mut out),
+        },
 
-        // Команды для двусвязного списка
-        "LPUSH_HEAD" => {
-            if parts.len() > 1 {
-                ds.doubly_list.add_head(parts[1].to_string());
-                println!("Added \"{}\" to doubly list head", parts[1]);
-            }
-        }
-        "LPUSH_TAIL" => {
-            if parts.len() > 1 {
-                ds.doubly_list.add_tail(parts[1].to_string());
-                println!("Added \"{}\" to doubly list tail", parts[1]);
-            }
-        }
-        "LDEL_HEAD" => {
-            match ds.doubly_list.remove_head() {
-                Ok(_) => println!("Removed doubly list head"),
-                Err(e) => println!("Error: {}", e),
-            }
+        // Команды структур данных
+        _ => ds.execute(&call, &mut out),
+    }
+
+    // Сообщения, накопленные структурами во время команды (например, о вытеснении из кэша)
+    out.lines.extend(ds.notices.borrow_mut().drain(..));
+    out
+}
+
+// Команды записи истории для UNDO/REDO. Если одна из них не выполнилась, все изменения
+// откатываются, а результат получает её статус и вид ошибки
+fn replay(commands: &[String], ds: &mut DataStructures, out: &mut CommandResult) -> bool {
+    let snapshot = match ds.snapshot() {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            out.fail_as(ErrorKind::Failed, e);
+            return false;
+        }
+    };
+
+    ds.history.set_replaying(true);
+    let mut failed = None;
+    for command in commands {
+        let replayed = replay_command(command, ds);
+        out.lines.extend(replayed.lines);
+        if replayed.status == Status::Error {
+            failed = Some((replayed.kind.unwrap_or(ErrorKind::Failed), replayed.error.unwrap_or_default()));
+            break;
         }
-        "LDEL_TAIL" => {
-            match ds.doubly_list.remove_tail() {
-                Ok(_) => println!("Removed doubly list tail"),
-                Err(e) => println!("Error: {}", e),
+    }
+    ds.history.set_replaying(false);
+
+    let Some((kind, error)) = failed else {
+        return true;
+    };
+    if let Err(e) = ds.rollback(snapshot) {
+        out.line(format!("Error: {}", e));
+    }
+    out.set_error(kind, error);
+    false
+}
+
+// _RESTORE нет в таблице команд, поэтому ввести её нельзя: обратные команды
+// восстановления выполняются здесь напрямую
+fn replay_command(command: &str, ds: &mut DataStructures) -> CommandResult {
+    let parts: Vec<&str> = command.split_whitespace().collect();
+    if parts.first() != Some(&"_RESTORE") {
+        return process_command(command, ds);
+    }
+    let mut out = CommandResult::new(command);
+    match parts.get(1) {
+        Some(target) => {
+            if let Err(e) = ds.restore(target, &parts[2..]) {
+                out.fail_as(ErrorKind::Failed, e);
             }
         }
-        "LSEARCH" => {
-            if parts.len() > 1 {
-                let found = ds.doubly_list.find(parts[1]);
-                println!("Value \"{}\" {}", parts[1], if found { "found" } else { "not found" });
+        None => out.fail_as(ErrorKind::BadArguments, "Restore target is missing"),
+    }
+    out
+}
+
+// Команда, введённая пользователем. Ошибка внутри транзакции откатывает все изменения
+// с BEGIN, а следующие команды пропускаются до COMMIT или ROLLBACK.
+// Поиск, не нашедший значения, транзакцию не прерывает.
+fn process_in_transaction(query: &str, ds: &mut DataStructures) -> CommandResult {
+    let command = query.split_whitespace().next().unwrap_or("").to_uppercase();
+    let control = matches!(command.as_str(), "BEGIN" | "COMMIT" | "ROLLBACK");
+    if !control && ds.transaction.as_ref().is_some_and(|transaction| transaction.is_aborted()) {
+        let mut out = CommandResult::new(query);
+        out.fail_as(ErrorKind::Failed, "Transaction aborted, commands are ignored until COMMIT or ROLLBACK");
+        return out;
+    }
+
+    let mut out = process_command(query, ds);
+    // Ненайденное значение при поиске — обычный ответ, а не сбой изменения
+    let missed = find_command(ds, &command).is_some_and(|spec| spec.lookup)
+        && matches!(out.kind, Some(ErrorKind::NotFound | ErrorKind::Empty));
+    if !control && !missed && out.exit_code() != 0 {
+        if let Some(Transaction::Active(snapshot)) = ds.transaction.take() {
+            if let Err(e) = ds.rollback(snapshot) {
+                out.line(format!("Error: {}", e));
             }
+            ds.transaction = Some(Transaction::Aborted);
+            out.line("Transaction aborted, changes rolled back");
         }
-        "LPRINT_FORWARD" => {
-            print!("Doubly List (forward): ");
-            ds.doubly_list.print_forward();
-        }
-        "LPRINT_BACKWARD" => {
-            print!("Doubly List (backward): ");
-            ds.doubly_list.print_backward();
-        }
+    }
+    out
+}
 
-        // Команды для стека
-        "SPUSH" => {
-            if parts.len() > 1 {
-                ds.stack.push(parts[1].to_string());
-                println!("Pushed \"{}\" to stack", parts[1]);
-            }
+fn run_command(query: &str, ds: &mut DataStructures, format: OutputFormat) -> i32 {
+    let result = process_in_transaction(query, ds);
+    let rendered = result.render(format);
+    if !rendered.is_empty() {
+        println!("{}", rendered);
+    }
+    result.exit_code()
+}
+
+// Выполнение команд из файла: пустые строки и строки с # пропускаются.
+// Ошибка команды не прерывает сценарий; возвращается код первой ошибки.
+fn run_script(path: &str, ds: &mut DataStructures, format: OutputFormat) -> Result<i32, String> {
+    let file = File::open(path).map_err(|e| format!("Cannot open script {}: {}", path, e))?;
+    let mut exit_code = 0;
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| format!("Cannot read script {}: {}", path, e))?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let code = run_command(line, ds, format);
+        if exit_code == 0 {
+            exit_code = code;
         }
-        "SPOP" => {
-            match ds.stack.pop() {
-                Ok(value) => println!("Popped from stack: \"{}\"", value),
-                Err(e) => println!("Error: {}", e),
-            }
+    }
+    Ok(exit_code)
+}
+
+// Интерактивный режим: команды читаются из stdin до EXIT или конца ввода.
+// Ошибки команд видны сразу, поэтому на код завершения они не влияют.
+fn run_repl(ds: &mut DataStructures, format: OutputFormat) -> Result<i32, String> {
+    let stdin = io::stdin();
+    loop {
+        // В режиме JSON приглашение не выводится, чтобы каждая строка была объектом
+        if format == OutputFormat::Text {
+            print!("> ");
+            io::stdout().flush().ok();
         }
-        "SPEEK" => {
-            match ds.stack.peek() {
-                Ok(value) => println!("Stack top: \"{}\"", value),
-                Err(e) => println!("Error: {}", e),
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).map_err(|e| format!("Cannot read input: {}", e))? == 0 {
+            if format == OutputFormat::Text {
+                println!();
             }
+            return Ok(0);
         }
-        "SEMPTY" => {
-            println!("Stack is {}", if ds.stack.is_empty() { "empty" } else { "not empty" });
+        let line = line.trim();
+        if line.eq_ignore_ascii_case("EXIT") || line.eq_ignore_ascii_case("QUIT") {
+            return Ok(0);
         }
-        "SPRINT" => {
-            print!("Stack: ");
-            for value in ds.stack.iter() {
-                print!("{} ", value);
-            }
-            println!();
+        if !line.is_empty() {
+            run_command(line, ds, format);
         }
+    }
+}
 
-        // Команды для очереди
-        "QPUSH" => {
-            if parts.len() > 1 {
-                ds.queue.enqueue(parts[1].to_string());
-                println!("Enqueued \"{}\"", parts[1]);
-            }
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let program = args.first().map(|arg| arg.as_str()).unwrap_or("lab");
+
+    let options = match cli::parse_args(&args) {
+        Ok(Action::Run(options)) => options,
+        Ok(Action::Help) => {
+            println!("{}", cli::usage(program));
+            return;
         }
-        "QPOP" => {
-            match ds.queue.dequeue() {
-                Ok(value) => println!("Dequeued: \"{}\"", value),
-                Err(e) => println!("Error: {}", e),
-            }
+        Ok(Action::Version) => {
+            println!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+            return;
         }
-        "QPEEK" => {
-            match ds.queue.peek() {
-                Ok(value) => println!("Queue front: \"{}\"", value),
-                Err(e) => println!("Error: {}", e),
-            }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!("Run {} --help for usage", program);
+            process::exit(ErrorKind::BadArguments.exit_code());
         }
-        "QEMPTY" => {
-            println!("Queue is {}", if ds.queue.is_empty() { "empty" } else { "not empty" });
-        }
-        "QPRINT" => {
-            print!("Queue: ");
-            for value in ds.queue.iter() {
-                print!("{} ", value);
-            }
-            println!();
+    };
+
+    if let Some(dir) = &options.data_dir {
+        if let Err(e) = fs::create_dir_all(dir) {
+            eprintln!("Error: Cannot create data directory {}: {}", dir, e);
+            process::exit(ErrorKind::Io.exit_code());
         }
+    }
+    let base_filename = options.base_filename();
 
-        // Команды для дерева
-        "TINSERT" => {
-            if parts.len() > 1 {
-                ds.tree.insert(parts[1].to_string());
-                println!("Inserted \"{}\" into tree", parts[1]);
-            }
+    // Инициализация структур
+    let mut ds = create_data_structures(options.queue_backend);
+
+    // Загрузка данных из файлов; при ошибке ничего не выполняется и не сохраняется
+    if let Err(e) = load_from_files(&base_filename, &mut ds, options.quiet) {
+        eprintln!("Error: {}", e);
+        process::exit(ErrorKind::Io.exit_code());
+    }
+
+    // Выполнение команд
+    let result = match &options.mode {
+        Mode::Query(query) => Ok(run_command(query, &mut ds, options.format)),
+        Mode::Script(path) => run_script(path, &mut ds, options.format),
+        Mode::Repl => run_repl(&mut ds, options.format),
+    };
+    let mut exit_code = match result {
+        Ok(exit_code) => exit_code,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(ErrorKind::Io.exit_code());
         }
-        "TSEARCH" => {
-            if parts.len() > 1 {
-                let found = ds.tree.search(parts[1]);
-                println!("Value \"{}\" {} in tree", parts[1], if found { "found" } else { "not found" });
+    };
+
+    // Незавершённая транзакция откатывается, чтобы сохранились только завершённые группы
+    if let Some(transaction) = ds.transaction.take() {
+        if let Transaction::Active(snapshot) = transaction {
+            if let Err(e) = ds.rollback(snapshot) {
+                eprintln!("Error: {}", e);
             }
         }
-        "TISFULL" => {
-            println!("Tree is {}", if ds.tree.is_full() { "full" } else { "not full" });
+        eprintln!("Error: Transaction was not committed, changes rolled back");
+        if exit_code == 0 {
+            exit_code = ErrorKind::Failed.exit_code();
         }
-        "TPRINT" => {
-            ds.tree.print_inorder();
+    }
+
+    // Сохранение данных в файлы
+    if options.dry_run {
+        if !options.quiet {
+            println!("Dry run: changes were not saved");
         }
+    } else if let Err(e) = save_to_files(&base_filename, &ds, options.quiet) {
+        eprintln!("Error: {}", e);
+        process::exit(ErrorKind::Io.exit_code());
+    }
+
+    process::exit(exit_code);
+}
+
+// Общие помощники для тестов команд
+#[cfg(test)]
+mod testing {
+    use crate::output::CommandResult;
+    use crate::queue_commands::QueueBackend;
+    use crate::registry::DataStructures;
 
-        _ => {
-            println!("Unknown command: {}", command);
-            println!("Available commands:");
-            println!("Array: MPUSH_BACK, MPUSH_INDEX, MDEL, MGET, MREPLACE, MLEN, MPRINT");
-            println!("Singly List: FPUSH_HEAD, FPUSH_TAIL, FDEL_HEAD, FDEL_TAIL, FDEL_VALUE, FSEARCH, FPRINT");
-            println!("Doubly List: LPUSH_HEAD, LPUSH_TAIL, LDEL_HEAD, LDEL_TAIL, LSEARCH, LPRINT_FORWARD, LPRINT_BACKWARD");
-            println!("Stack: SPUSH, SPOP, SPEEK, SEMPTY, SPRINT");
-            println!("Queue: QPUSH, QPOP, QPEEK, QEMPTY, QPRINT");
-            println!("Tree: TINSERT, TSEARCH, TISFULL, TPRINT");
+    pub fn structures() -> DataStructures {
+        crate::create_data_structures(QueueBackend::Linked)
+    }
+
+    // Выполняет команды как при вводе пользователем и возвращает результат последней
+    pub fn run(ds: &mut DataStructures, queries: &[&str]) -> CommandResult {
+        let mut last = None;
+        for query in queries {
+            last = Some(crate::process_in_transaction(query, ds));
         }
+        last.expect("at least one query")
+    }
+
+    // Текстовый вывод одной команды
+    pub fn printed(ds: &mut DataStructures, query: &str) -> String {
+        run(ds, &[query]).lines.join("\n")
     }
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
+#[cfg(test)]
+mod tests {
+    use crate::history::HistoryEntry;
+    use crate::output::{ErrorKind, Status};
+    use crate::testing::{printed, run, structures};
+
+    #[test]
+    fn undo_and_redo_replay_commands() {
+        let mut ds = structures();
+        run(&mut ds, &["SPUSH a", "SPUSH b"]);
+        assert_eq!(printed(&mut ds, "UNDO"), "Popped from stack: \"b\"\nUndone: SPUSH b");
+        assert_eq!(printed(&mut ds, "SLEN"), "Stack size: 1");
+        let redone = run(&mut ds, &["REDO"]);
+        assert_eq!(redone.status, Status::Ok);
+        assert_eq!(redone.lines.last().unwrap(), "Redone: SPUSH b");
+        assert_eq!(printed(&mut ds, "SLEN"), "Stack size: 2");
+        assert_eq!(run(&mut ds, &["REDO"]).kind, Some(ErrorKind::Empty));
+        run(&mut ds, &["UNDO", "UNDO"]);
+        assert_eq!(run(&mut ds, &["UNDO"]).kind, Some(ErrorKind::Empty));
+    }
 
-    if args.len() != 5 {
-        println!("Usage: {} --file <filename> --query <command>", args[0]);
-        println!("Example: {} --file data --query 'MPUSH_BACK hello'", args[0]);
-        return;
+    #[test]
+    fn failed_undo_keeps_entry_and_state() {
+        let mut ds = structures();
+        run(&mut ds, &["SPUSH a", "SPUSH b", "LPUSH_TAIL c"]);
+        ds.history.push_undo(HistoryEntry {
+            command: "FORGED".to_string(),
+            inverse: vec!["SPOP".to_string(), "LDEL_VALUE missing".to_string()],
+        });
+
+        let undone = run(&mut ds, &["UNDO"]);
+        assert_eq!(undone.status, Status::Error);
+        assert_eq!(undone.kind, Some(ErrorKind::NotFound));
+        assert_eq!(undone.lines.last().unwrap(), "Cannot undo: FORGED");
+        // SPOP откатился вместе с остальной записью
+        assert_eq!(printed(&mut ds, "SLEN"), "Stack size: 2");
+        assert_eq!(ds.history.undo_entries().last().unwrap().command, "FORGED");
+        assert_eq!(ds.history.redo_entries().count(), 0);
     }
 
-    let mut base_filename = String::new();
-    let mut query = String::new();
+    #[test]
+    fn failed_redo_keeps_entry() {
+        let mut ds = structures();
+        ds.history.push_redo(HistoryEntry { command: "SPOP".to_string(), inverse: vec![] });
+        let redone = run(&mut ds, &["REDO"]);
+        assert_eq!(redone.kind, Some(ErrorKind::Empty));
+        assert_eq!(ds.history.redo_entries().count(), 1);
+        assert_eq!(ds.history.undo_entries().count(), 0);
+    }
 
-    let mut i = 1;
-    while i < args.len() {
-        match args[i].as_str() {
-            "--file" => {
-                if i + 1 < args.len() {
-                    base_filename = args[i + 1].clone();
-                    i += 1;
-                }
-            }
-            "--query" => {
-                if i + 1 < args.len() {
-                    query = args[i + 1].clone();
-                    i += 1;
-                }
-            }
-            _ => {}
-        }
-        i += 1;
+    #[test]
+    fn restore_is_not_a_user_command() {
+        let mut ds = structures();
+        run(&mut ds, &["SPUSH a"]);
+        assert_eq!(run(&mut ds, &["_RESTORE stack"]).kind, Some(ErrorKind::UnknownCommand));
+        assert_eq!(printed(&mut ds, "SLEN"), "Stack size: 1");
     }
 
-    if base_filename.is_empty() || query.is_empty() {
-        println!("Error: Missing required arguments");
-        return;
+    #[test]
+    fn undo_restores_dropped_instance() {
+        let mut ds = structures();
+        run(&mut ds, &["CREATE STACK other", "SPUSH other x", "SPUSH other y", "DROP STACK other"]);
+        assert_eq!(run(&mut ds, &["SLEN other"]).kind, Some(ErrorKind::BadArguments));
+        run(&mut ds, &["UNDO"]);
+        assert_eq!(printed(&mut ds, "SLEN other"), "Stack size: 2");
     }
 
-    // Инициализация структур
-    let mut ds = DataStructures::new();
+    #[test]
+    fn history_depth_limits_undo() {
+        let mut ds = structures();
+        assert_eq!(printed(&mut ds, "HISTORY_DEPTH 1"), "History depth set to 1");
+        run(&mut ds, &["SPUSH a", "SPUSH b"]);
+        assert_eq!(printed(&mut ds, "HISTORY"), "Undo (oldest first):\n  SPUSH b\nRedo (oldest first):");
+        run(&mut ds, &["UNDO"]);
+        assert_eq!(run(&mut ds, &["UNDO"]).kind, Some(ErrorKind::Empty));
+        assert_eq!(printed(&mut ds, "HISTORY_DEPTH"), "History depth: 1");
+    }
 
-    // Загрузка данных из файлов
-    load_from_files(&base_filename, &mut ds);
+    #[test]
+    fn instances_are_created_renamed_and_dropped() {
+        let mut ds = structures();
+        assert_eq!(printed(&mut ds, "CREATE STACK work"), "Created stack \"work\"");
+        run(&mut ds, &["SPUSH work a", "SPUSH work b", "SPUSH x"]);
+        assert_eq!(printed(&mut ds, "SLEN work"), "Stack size: 2");
+        assert_eq!(printed(&mut ds, "SLEN"), "Stack size: 1");
+        assert_eq!(printed(&mut ds, "INSTANCES stack"), "stack: default, work");
+
+        assert_eq!(printed(&mut ds, "RENAME STACK work jobs"), "Renamed stack \"work\" to \"jobs\"");
+        assert_eq!(run(&mut ds, &["SLEN work"]).lines, ["Error: SLEN takes no arguments"]);
+        // Обратные команды записываются с именем экземпляра
+        run(&mut ds, &["SPOP jobs", "UNDO"]);
+        assert_eq!(printed(&mut ds, "SLEN jobs"), "Stack size: 2");
+        run(&mut ds, &["UNDO"]);
+        assert_eq!(printed(&mut ds, "SLEN work"), "Stack size: 2");
+
+        run(&mut ds, &["DROP STACK work"]);
+        assert_eq!(printed(&mut ds, "INSTANCES stack"), "stack: default");
+        run(&mut ds, &["UNDO"]);
+        assert_eq!(printed(&mut ds, "SLEN work"), "Stack size: 2");
+    }
 
-    // Выполнение команды
-    process_command(&query, &mut ds);
+    #[test]
+    fn instance_errors() {
+        let mut ds = structures();
+        run(&mut ds, &["CREATE QUEUE q"]);
+        for query in ["CREATE QUEUE q", "CREATE QUEUE 9q", "CREATE NOPE x", "CREATE EXPRESSION e", "DROP QUEUE default", "RENAME QUEUE q default", "DROP QUEUE other"] {
+            assert_eq!(run(&mut ds, &[query]).kind, Some(ErrorKind::Failed), "{}", query);
+        }
+        assert_eq!(run(&mut ds, &["INSTANCES nope"]).kind, Some(ErrorKind::NotFound));
+        assert_eq!(run(&mut ds, &["QPUSH missing"]).lines, ["Enqueued \"missing\""]);
+        assert_eq!(run(&mut ds, &["QPUSH q v"]).lines, ["Enqueued \"v\""]);
+        assert_eq!(run(&mut ds, &["QPUSH other v"]).kind, Some(ErrorKind::BadArguments));
+    }
 
-    // Сохранение данных в файлы
-    save_to_files(&base_filename, &ds);
-}
\ No newline at end of file
+    #[test]
+    fn ambiguous_first_arguments() {
+        let mut ds = structures();
+        // Без экземпляра "a" это ошибка в аргументах, а не поиск экземпляра
+        let result = run(&mut ds, &["SPUSH a b"]);
+        assert_eq!(result.kind, Some(ErrorKind::BadArguments));
+        assert_eq!(result.lines, ["Error: SPUSH expects <value>"]);
+        assert_eq!(run(&mut ds, &["FPUSH_HEAD a\tb"]).lines, ["Error: FPUSH_HEAD expects <value>"]);
+
+        // Аргументы, верные и без имени, относятся к экземпляру по умолчанию
+        run(&mut ds, &["CREATE STACK numeric", "CREATE STACK a"]);
+        assert_eq!(printed(&mut ds, "SCOMPARATOR numeric"), "Stack comparator set to numeric");
+        assert_eq!(printed(&mut ds, "SCOMPARATOR"), "Stack comparator: numeric");
+        assert_eq!(printed(&mut ds, "SCOMPARATOR numeric lex"), "Stack comparator set to lexicographic");
+        assert_eq!(printed(&mut ds, "SCOMPARATOR numeric"), "Stack comparator set to numeric");
+        assert_eq!(printed(&mut ds, "SCOMPARATOR a"), "Stack comparator: lexicographic");
+        assert_eq!(printed(&mut ds, "SPUSH a"), "Pushed \"a\" to stack");
+        assert_eq!(printed(&mut ds, "SPUSH a b"), "Pushed \"b\" to stack");
+        assert_eq!(printed(&mut ds, "SLEN a"), "Stack size: 1");
+        assert_eq!(printed(&mut ds, "SLEN"), "Stack size: 1");
+    }
+
+    #[test]
+    fn instances_survive_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("lab-instances-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let base = dir.join("data").to_string_lossy().into_owned();
+
+        let mut ds = structures();
+        run(&mut ds, &["CREATE STACK work", "SPUSH work a", "CREATE STACK old", "SPUSH b"]);
+        crate::save_to_files(&base, &ds, true).unwrap();
+        assert!(std::path::Path::new(&format!("{}_stack.work.txt", base)).exists());
+
+        let mut loaded = structures();
+        crate::load_from_files(&base, &mut loaded, true).unwrap();
+        assert_eq!(printed(&mut loaded, "INSTANCES stack"), "stack: default, work, old");
+        assert_eq!(printed(&mut loaded, "SLEN work"), "Stack size: 1");
+        assert_eq!(printed(&mut loaded, "SLEN"), "Stack size: 1");
+
+        // Файлы удалённого экземпляра убираются при сохранении
+        run(&mut loaded, &["DROP STACK old"]);
+        crate::save_to_files(&base, &loaded, true).unwrap();
+        assert!(!std::path::Path::new(&format!("{}_stack.old.txt", base)).exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn history_with_tabs_survives_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("lab-history-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let base = dir.join("data").to_string_lossy().into_owned();
+
+        let mut ds = structures();
+        run(&mut ds, &["FPUSH_HEAD\tx", "FPUSH_HEAD  y"]);
+        crate::save_to_files(&base, &ds, true).unwrap();
+
+        let mut loaded = structures();
+        crate::load_from_files(&base, &mut loaded, true).unwrap();
+        assert_eq!(
+            printed(&mut loaded, "HISTORY"),
+            "Undo (oldest first):\n  FPUSH_HEAD x\n  FPUSH_HEAD y\nRedo (oldest first):"
+        );
+        run(&mut loaded, &["UNDO"]);
+        let undone = run(&mut loaded, &["UNDO"]);
+        assert_eq!(undone.exit_code(), 0);
+        assert_eq!(undone.lines.last().unwrap(), "Undone: FPUSH_HEAD x");
+        assert_eq!(printed(&mut loaded, "FLEN"), "Singly list length: 0");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
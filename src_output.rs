@@ -0,0 +1,322 @@
+use crate::cli::OutputFormat;
+
+// Результат выполнения команды. Команды не печатают сами:
+// результат выводится текстом или в JSON в зависимости от --output.
+
+pub enum Value {
+    Text(String),
+    // Уже отформатированное число
+    Number(String),
+    Bool(bool),
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Text(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Text(value)
+    }
+}
+
+impl From<&String> for Value {
+    fn from(value: &String) -> Self {
+        Value::Text(value.clone())
+    }
+}
+
+impl From<usize> for Value {
+    fn from(value: usize) -> Self {
+        Value::Number(value.to_string())
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Number(value.to_string())
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl Value {
+    fn to_json(&self) -> String {
+        match self {
+            Value::Text(text) => json_string(text),
+            // inf и NaN не являются числами JSON
+            Value::Number(number) if number.parse::<f64>().is_ok_and(|number| number.is_finite()) => number.clone(),
+            Value::Number(number) => json_string(number),
+            Value::Bool(flag) => flag.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Status {
+    Ok,
+    Error,
+}
+
+// Вид ошибки определяет код завершения программы
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ErrorKind {
+    // Прочие ошибки: переполнение, деление на ноль и т. п.
+    Failed,
+    BadArguments,
+    NotFound,
+    Empty,
+    UnknownCommand,
+    Io,
+}
+
+impl ErrorKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ErrorKind::Failed => "failed",
+            ErrorKind::BadArguments => "bad_arguments",
+            ErrorKind::NotFound => "not_found",
+            ErrorKind::Empty => "empty",
+            ErrorKind::UnknownCommand => "unknown_command",
+            ErrorKind::Io => "io",
+        }
+    }
+
+    // 0 — успех; 2 совпадает с кодом ошибки в аргументах командной строки
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ErrorKind::Failed => 1,
+            ErrorKind::BadArguments => 2,
+            ErrorKind::NotFound => 3,
+            ErrorKind::Empty => 4,
+            ErrorKind::UnknownCommand => 5,
+            ErrorKind::Io => 6,
+        }
+    }
+}
+
+pub struct CommandResult {
+    pub command: String,
+    pub status: Status,
+    pub value: Option<Value>,
+    pub values: Option<Vec<Value>>,
+    pub error: Option<String>,
+    pub kind: Option<ErrorKind>,
+    // Строки текстового вывода
+    pub lines: Vec<String>,
+}
+
+impl CommandResult {
+    pub fn new(command: &str) -> Self {
+        CommandResult {
+            command: command.trim().to_string(),
+            status: Status::Ok,
+            value: None,
+            values: None,
+            error: None,
+            kind: None,
+            lines: Vec::new(),
+        }
+    }
+
+    pub fn line<S: Into<String>>(&mut self, text: S) {
+        self.lines.push(text.into());
+    }
+
+    pub fn set_value<V: Into<Value>>(&mut self, value: V) {
+        self.value = Some(value.into());
+    }
+
+    pub fn set_values<V: Into<Value>, I: IntoIterator<Item = V>>(&mut self, values: I) {
+        self.values = Some(values.into_iter().map(|value| value.into()).collect());
+    }
+
+    // Ошибка без строки "Error: ..." в текстовом выводе
+    pub fn set_error<S: Into<String>>(&mut self, kind: ErrorKind, error: S) {
+        self.status = Status::Error;
+        self.kind = Some(kind);
+        self.error = Some(error.into());
+    }
+
+    pub fn fail_as<S: Into<String>>(&mut self, kind: ErrorKind, error: S) {
+        let error = error.into();
+        self.lines.push(format!("Error: {}", error));
+        self.set_error(kind, error);
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        self.kind.map_or(0, |kind| kind.exit_code())
+    }
+
+    // Текст без завершающего перевода строки; пустая строка, если выводить нечего
+    pub fn render(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Text => self.lines.join("\n"),
+            OutputFormat::Json => self.to_json(),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        let mut fields = vec![
+            format!("\"command\":{}", json_string(&self.command)),
+            format!(
+                "\"status\":\"{}\"",
+                match self.status {
+                    Status::Ok => "ok",
+                    Status::Error => "error",
+                }
+            ),
+        ];
+        if let Some(value) = &self.value {
+            fields.push(format!("\"value\":{}", value.to_json()));
+        }
+        if let Some(values) = &self.values {
+            let values: Vec<String> = values.iter().map(|value| value.to_json()).collect();
+            fields.push(format!("\"values\":[{}]", values.join(",")));
+        }
+        if let Some(error) = &self.error {
+            fields.push(format!("\"error\":{}", json_string(error)));
+        }
+        if let Some(kind) = &self.kind {
+            fields.push(format!("\"kind\":\"{}\"", kind.name()));
+        }
+        let messages: Vec<String> = self.lines.iter().map(|line| json_string(line)).collect();
+        fields.push(format!("\"messages\":[{}]", messages.join(",")));
+        format!("{{{}}}", fields.join(","))
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{json_string, CommandResult, ErrorKind, Value};
+    use crate::cli::OutputFormat;
+    use crate::testing::{run, structures};
+
+    #[test]
+    fn strings_are_escaped() {
+        assert_eq!(json_string("plain"), "\"plain\"");
+        assert_eq!(json_string("a\"b\\c"), "\"a\\\"b\\\\c\"");
+        assert_eq!(json_string("1\n2\r3\t4"), "\"1\\n2\\r3\\t4\"");
+        assert_eq!(json_string("\u{1}\u{1f}"), "\"\\u0001\\u001f\"");
+        assert_eq!(json_string("ünï ✓"), "\"ünï ✓\"");
+    }
+
+    #[test]
+    fn values_keep_their_json_type() {
+        assert_eq!(Value::from(42usize).to_json(), "42");
+        assert_eq!(Value::from(-7i64).to_json(), "-7");
+        assert_eq!(Value::from("7").to_json(), "\"7\"");
+        assert_eq!(Value::from(true).to_json(), "true");
+        assert_eq!(Value::Number("2.5".to_string()).to_json(), "2.5");
+        // inf и NaN выводятся строками
+        for number in ["inf", "-inf", "NaN"] {
+            assert_eq!(Value::Number(number.to_string()).to_json(), format!("\"{}\"", number));
+        }
+    }
+
+    #[test]
+    fn success_and_error_render() {
+        let mut result = CommandResult::new(" SPOP ");
+        result.line("Popped from stack: \"a\"");
+        result.set_value("a");
+        assert_eq!(result.render(OutputFormat::Text), "Popped from stack: \"a\"");
+        assert_eq!(
+            result.render(OutputFormat::Json),
+            r#"{"command":"SPOP","status":"ok","value":"a","messages":["Popped from stack: \"a\""]}"#
+        );
+        assert_eq!(result.exit_code(), 0);
+
+        let mut result = CommandResult::new("SPOP");
+        result.fail_as(ErrorKind::Empty, "Stack is empty");
+        assert_eq!(
+            result.render(OutputFormat::Json),
+            r#"{"command":"SPOP","status":"error","error":"Stack is empty","kind":"empty","messages":["Error: Stack is empty"]}"#
+        );
+        assert_eq!(result.exit_code(), 4);
+
+        // Ошибка без строки вывода
+        let mut result = CommandResult::new("X");
+        result.set_error(ErrorKind::Io, "disk");
+        assert_eq!(result.render(OutputFormat::Text), "");
+        assert_eq!(result.exit_code(), 6);
+    }
+
+    #[test]
+    fn commands_fill_values() {
+        let mut ds = structures();
+        run(&mut ds, &["SPUSH a", "SPUSH b"]);
+        let printed = run(&mut ds, &["SLEN"]).render(OutputFormat::Json);
+        assert!(printed.contains(r#""value":2"#), "{}", printed);
+        let printed = run(&mut ds, &["SEMPTY"]).render(OutputFormat::Json);
+        assert!(printed.contains(r#""value":false"#), "{}", printed);
+        let printed = run(&mut ds, &["EVAL 1 / 0"]).render(OutputFormat::Json);
+        assert!(printed.contains(r#""status":"error""#), "{}", printed);
+    }
+
+    #[test]
+    fn command_errors_have_explicit_kinds() {
+        let mut ds = structures();
+        let cases = [
+            ("LDEL_VALUE a", ErrorKind::Empty),
+            ("FDEL_HEAD", ErrorKind::Empty),
+            ("SPOP", ErrorKind::Empty),
+            ("PQPEEK", ErrorKind::Empty),
+            ("UNDO", ErrorKind::Empty),
+            ("MGET 3", ErrorKind::NotFound),
+            ("FINSERT 4 x", ErrorKind::NotFound),
+            ("EVAL 1 / 0", ErrorKind::Failed),
+            ("COMMIT", ErrorKind::Failed),
+            ("DROP STACK missing", ErrorKind::Failed),
+            ("NOPE", ErrorKind::UnknownCommand),
+            ("MGET x", ErrorKind::BadArguments),
+        ];
+        for (query, kind) in cases {
+            let result = run(&mut ds, &[query]);
+            assert_eq!(result.kind, Some(kind), "{}", query);
+            assert_eq!(result.exit_code(), kind.exit_code(), "{}", query);
+        }
+
+        run(&mut ds, &["LPUSH_TAIL a"]);
+        assert_eq!(run(&mut ds, &["LDEL_VALUE b"]).kind, Some(ErrorKind::NotFound));
+    }
+
+    #[test]
+    fn kinds_have_distinct_codes_and_names() {
+        let kinds = [
+            ErrorKind::Failed,
+            ErrorKind::BadArguments,
+            ErrorKind::NotFound,
+            ErrorKind::Empty,
+            ErrorKind::UnknownCommand,
+            ErrorKind::Io,
+        ];
+        let codes: Vec<i32> = kinds.iter().map(|kind| kind.exit_code()).collect();
+        assert_eq!(codes, [1, 2, 3, 4, 5, 6]);
+        let names: Vec<&str> = kinds.iter().map(|kind| kind.name()).collect();
+        assert_eq!(names, ["failed", "bad_arguments", "not_found", "empty", "unknown_command", "io"]);
+    }
+}
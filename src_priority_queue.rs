@@ -0,0 +1,197 @@
+// Очередь с приоритетами на двоичной куче.
+// Больший приоритет извлекается раньше, при равных — в порядке добавления.
+pub struct PriorityEntry {
+    pub priority: i64,
+    // Порядковый номер добавления, сохраняет FIFO для равных приоритетов
+    pub seq: u64,
+    pub value: String,
+}
+
+impl PriorityEntry {
+    fn outranks(&self, other: &PriorityEntry) -> bool {
+        self.priority > other.priority || (self.priority == other.priority && self.seq < other.seq)
+    }
+}
+
+pub struct PriorityQueue {
+    heap: Vec<PriorityEntry>,
+    next_seq: u64,
+}
+
+impl PriorityQueue {
+    pub fn new() -> Self {
+        PriorityQueue {
+            heap: Vec::new(),
+            next_seq: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    // Номер, который получит следующий добавленный элемент
+    pub fn next_seq(&self) -> u64 {
+        self.next_seq
+    }
+
+    pub fn set_next_seq(&mut self, next_seq: u64) {
+        self.next_seq = self.next_seq.max(next_seq);
+    }
+
+    // Возвращает порядковый номер нового элемента
+    pub fn push(&mut self, priority: i64, value: String) -> u64 {
+        let seq = self.next_seq;
+        self.push_with_seq(seq, priority, value);
+        seq
+    }
+
+    // Добавление с уже известным номером (при загрузке из файла).
+    // Номера из файла могут быть любыми, поэтому следующий номер не переполняется
+    pub fn push_with_seq(&mut self, seq: u64, priority: i64, value: String) {
+        self.next_seq = self.next_seq.max(seq.saturating_add(1));
+        self.heap.push(PriorityEntry { priority, seq, value });
+        self.sift_up(self.heap.len() - 1);
+    }
+
+    pub fn pop(&mut self) -> Result<PriorityEntry, String> {
+        if self.heap.is_empty() {
+            return Err("Priority queue is empty".to_string());
+        }
+        let entry = self.heap.swap_remove(0);
+        if !self.heap.is_empty() {
+            self.sift_down(0);
+        }
+        Ok(entry)
+    }
+
+    pub fn peek(&self) -> Result<&PriorityEntry, String> {
+        self.heap.first().ok_or_else(|| "Priority queue is empty".to_string())
+    }
+
+    // Меняет приоритет самого раннего элемента с данным значением.
+    // Порядковый номер сохраняется. Возвращает прежний приоритет.
+    pub fn change_priority(&mut self, value: &str, priority: i64) -> Result<i64, String> {
+        let index = self
+            .heap
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.value == value)
+            .min_by_key(|(_, entry)| entry.seq)
+            .map(|(index, _)| index)
+            .ok_or_else(|| "Value not found".to_string())?;
+
+        let old = std::mem::replace(&mut self.heap[index].priority, priority);
+        if priority > old {
+            self.sift_up(index);
+        } else {
+            self.sift_down(index);
+        }
+        Ok(old)
+    }
+
+    // Элементы в порядке извлечения
+    pub fn sorted(&self) -> Vec<&PriorityEntry> {
+        let mut entries: Vec<&PriorityEntry> = self.heap.iter().collect();
+        entries.sort_by(|a, b| b.priority.cmp(&a.priority).then(a.seq.cmp(&b.seq)));
+        entries
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if !self.heap[index].outranks(&self.heap[parent]) {
+                break;
+            }
+            self.heap.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let left = 2 * index + 1;
+            let right = left + 1;
+            let mut top = index;
+            if left < self.heap.len() && self.heap[left].outranks(&self.heap[top]) {
+                top = left;
+            }
+            if right < self.heap.len() && self.heap[right].outranks(&self.heap[top]) {
+                top = right;
+            }
+            if top == index {
+                break;
+            }
+            self.heap.swap(index, top);
+            index = top;
+        }
+    }
+}
+
+impl Default for PriorityQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PriorityQueue;
+
+    fn drain(queue: &mut PriorityQueue) -> Vec<String> {
+        std::iter::from_fn(|| queue.pop().ok()).map(|entry| entry.value).collect()
+    }
+
+    #[test]
+    fn pops_by_priority_then_fifo() {
+        let mut queue = PriorityQueue::new();
+        for (priority, value) in [(1, "a"), (5, "b"), (1, "c"), (5, "d"), (-3, "e"), (2, "f")] {
+            queue.push(priority, value.to_string());
+        }
+        assert_eq!(queue.peek().unwrap().value, "b");
+        let sorted: Vec<String> = queue.sorted().iter().map(|entry| entry.value.clone()).collect();
+        assert_eq!(sorted, ["b", "d", "f", "a", "c", "e"]);
+        assert_eq!(drain(&mut queue), sorted);
+        assert_eq!(queue.pop().err(), Some("Priority queue is empty".to_string()));
+        assert!(queue.peek().is_err());
+    }
+
+    #[test]
+    fn change_priority_moves_earliest_match() {
+        let mut queue = PriorityQueue::new();
+        for (priority, value) in [(1, "x"), (3, "y"), (1, "x"), (2, "z")] {
+            queue.push(priority, value.to_string());
+        }
+        assert_eq!(queue.change_priority("x", 10), Ok(1));
+        assert_eq!(queue.change_priority("y", 0), Ok(3));
+        assert_eq!(queue.change_priority("w", 0), Err("Value not found".to_string()));
+        let order: Vec<(i64, u64)> = queue.sorted().iter().map(|entry| (entry.priority, entry.seq)).collect();
+        assert_eq!(order, [(10, 0), (2, 3), (1, 2), (0, 1)]);
+        assert_eq!(drain(&mut queue), ["x", "z", "x", "y"]);
+    }
+
+    #[test]
+    fn loaded_sequence_numbers_keep_order() {
+        let mut queue = PriorityQueue::new();
+        queue.set_next_seq(4);
+        queue.push_with_seq(7, 1, "late".to_string());
+        queue.push_with_seq(2, 1, "early".to_string());
+        assert_eq!(queue.next_seq(), 8);
+        queue.set_next_seq(3);
+        assert_eq!(queue.push(1, "new".to_string()), 8);
+        assert_eq!(drain(&mut queue), ["early", "late", "new"]);
+    }
+
+    #[test]
+    fn maximal_sequence_number_does_not_overflow() {
+        let mut queue = PriorityQueue::new();
+        queue.push_with_seq(u64::MAX, 0, "a".to_string());
+        assert_eq!(queue.next_seq(), u64::MAX);
+        assert_eq!(queue.push(0, "b".to_string()), u64::MAX);
+        assert_eq!(queue.len(), 2);
+    }
+}
@@ -0,0 +1,167 @@
+// Очередь на односвязном списке: добавление в хвост, извлечение из головы
+struct QNode {
+    data: String,
+    next: Option<Box<QNode>>,
+}
+
+pub struct Queue {
+    head: Option<Box<QNode>>,
+    tail: *mut QNode,
+    len: usize,
+}
+
+impl Queue {
+    pub fn new() -> Self {
+        Queue {
+            head: None,
+            tail: std::ptr::null_mut(),
+            len: 0,
+        }
+    }
+
+    pub fn enqueue(&mut self, value: String) {
+        let mut new_node = Box::new(QNode {
+            data: value,
+            next: None,
+        });
+        let raw_node: *mut _ = &mut *new_node;
+
+        if self.tail.is_null() {
+            self.head = Some(new_node);
+        } else {
+            unsafe {
+                (*self.tail).next = Some(new_node);
+            }
+        }
+        self.tail = raw_node;
+        self.len += 1;
+    }
+
+    pub fn dequeue(&mut self) -> Result<String, String> {
+        let mut old_head = self.head.take().ok_or_else(|| "Queue is empty".to_string())?;
+        self.head = old_head.next.take();
+
+        if self.head.is_none() {
+            self.tail = std::ptr::null_mut();
+        }
+        self.len -= 1;
+        Ok(old_head.data)
+    }
+
+    pub fn peek(&self) -> Result<&String, String> {
+        self.head
+            .as_ref()
+            .map(|node| &node.data)
+            .ok_or_else(|| "Queue is empty".to_string())
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    // Узлы освобождаются по одному, чтобы длинная цепочка не переполнила стек
+    pub fn clear(&mut self) {
+        let mut current = self.head.take();
+        while let Some(mut node) = current {
+            current = node.next.take();
+        }
+        self.tail = std::ptr::null_mut();
+        self.len = 0;
+    }
+
+    // От первого элемента к последнему
+    pub fn iter(&self) -> QueueIter<'_> {
+        QueueIter {
+            current: self.head.as_deref(),
+        }
+    }
+}
+
+// Без этого цепочка Box освобождалась бы рекурсивно
+impl Drop for Queue {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl Default for Queue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct QueueIter<'a> {
+    current: Option<&'a QNode>,
+}
+
+impl<'a> Iterator for QueueIter<'a> {
+    type Item = &'a String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.current.map(|node| {
+            self.current = node.next.as_deref();
+            &node.data
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue(values: &[&str]) -> Queue {
+        let mut queue = Queue::new();
+        for value in values {
+            queue.enqueue(value.to_string());
+        }
+        queue
+    }
+
+    #[test]
+    fn len_follows_enqueue_and_dequeue() {
+        let mut queue = queue(&["a", "b", "c"]);
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.dequeue(), Ok("a".to_string()));
+        assert_eq!(queue.len(), 2);
+        queue.dequeue().unwrap();
+        queue.dequeue().unwrap();
+        assert_eq!(queue.len(), 0);
+        assert!(queue.is_empty());
+        assert_eq!(queue.dequeue(), Err("Queue is empty".to_string()));
+        assert_eq!(queue.len(), 0);
+    }
+
+    #[test]
+    fn clear_resets_queue() {
+        let mut queue = queue(&["a", "b"]);
+        queue.clear();
+        assert_eq!(queue.len(), 0);
+        assert_eq!(queue.peek(), Err("Queue is empty".to_string()));
+        queue.enqueue("c".to_string());
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.iter().collect::<Vec<_>>(), ["c"]);
+    }
+
+    #[test]
+    fn iter_goes_from_front_to_back() {
+        let mut queue = queue(&["a", "b"]);
+        queue.dequeue().unwrap();
+        queue.enqueue("c".to_string());
+        assert_eq!(queue.iter().collect::<Vec<_>>(), ["b", "c"]);
+        assert_eq!(queue.peek(), Ok(&"b".to_string()));
+    }
+
+    #[test]
+    fn long_queue_drops_without_stack_overflow() {
+        let mut queue = Queue::new();
+        for i in 0..1_000_000 {
+            queue.enqueue(i.to_string());
+        }
+        assert_eq!(queue.len(), 1_000_000);
+        drop(queue);
+    }
+}
@@ -0,0 +1,680 @@
+use crate::circular_queue::{CircularQueue, OverflowPolicy};
+use crate::output::{CommandResult, ErrorKind};
+use crate::priority_queue::PriorityQueue;
+use crate::queue::Queue;
+use crate::registry::{restore_command, Call, CommandSpec, DataFile, DataStructures, Module};
+use crate::two_stack_queue::TwoStackQueue;
+
+// Команды очередей: обычной или кольцевой и с приоритетами
+
+// Реализация неограниченной очереди для команд Q*
+#[derive(Clone, Copy)]
+pub enum QueueBackend {
+    Linked,
+    TwoStacks,
+}
+
+impl QueueBackend {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "linked" => Some(QueueBackend::Linked),
+            "two-stacks" => Some(QueueBackend::TwoStacks),
+            _ => None,
+        }
+    }
+
+    fn new_queue(self) -> QueueMode {
+        match self {
+            QueueBackend::Linked => QueueMode::Unbounded(Queue::new()),
+            QueueBackend::TwoStacks => QueueMode::TwoStacks(TwoStackQueue::new()),
+        }
+    }
+}
+
+// Очередь для команд Q*: неограниченная или кольцевая фиксированной ёмкости
+enum QueueMode {
+    Unbounded(Queue),
+    TwoStacks(TwoStackQueue),
+    Circular(CircularQueue),
+}
+
+impl QueueMode {
+    // Для кольцевой очереди в режиме overwrite возвращает вытесненный элемент
+    fn enqueue(&mut self, value: String) -> Result<Option<String>, String> {
+        match self {
+            QueueMode::Unbounded(queue) => {
+                queue.enqueue(value);
+                Ok(None)
+            }
+            QueueMode::TwoStacks(queue) => {
+                queue.enqueue(value);
+                Ok(None)
+            }
+            QueueMode::Circular(queue) => queue.enqueue(value),
+        }
+    }
+
+    fn dequeue(&mut self) -> Result<String, String> {
+        match self {
+            QueueMode::Unbounded(queue) => queue.dequeue(),
+            QueueMode::TwoStacks(queue) => queue.dequeue(),
+            QueueMode::Circular(queue) => queue.dequeue(),
+        }
+    }
+
+    fn peek(&self) -> Result<&String, String> {
+        match self {
+            QueueMode::Unbounded(queue) => queue.peek(),
+            QueueMode::TwoStacks(queue) => queue.peek(),
+            QueueMode::Circular(queue) => queue.peek(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            QueueMode::Unbounded(queue) => queue.is_empty(),
+            QueueMode::TwoStacks(queue) => queue.is_empty(),
+            QueueMode::Circular(queue) => queue.is_empty(),
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        match self {
+            QueueMode::Unbounded(_) | QueueMode::TwoStacks(_) => false,
+            QueueMode::Circular(queue) => queue.is_full(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            QueueMode::Unbounded(queue) => queue.len(),
+            QueueMode::TwoStacks(queue) => queue.len(),
+            QueueMode::Circular(queue) => queue.len(),
+        }
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &String> + '_> {
+        match self {
+            QueueMode::Unbounded(queue) => Box::new(queue.iter()),
+            QueueMode::TwoStacks(queue) => Box::new(queue.iter()),
+            QueueMode::Circular(queue) => Box::new(queue.iter()),
+        }
+    }
+}
+
+pub struct QueueModule {
+    queue: QueueMode,
+    // Реализация неограниченной очереди, выбранная флагом --queue-backend
+    queue_backend: QueueBackend,
+}
+
+impl QueueModule {
+    pub fn new(queue_backend: QueueBackend) -> Self {
+        QueueModule {
+            queue: queue_backend.new_queue(),
+            queue_backend,
+        }
+    }
+
+    // Кольцевая очередь восстанавливается вместе с настройками и позицией головы
+    fn restore_command(&self) -> String {
+        match &self.queue {
+            QueueMode::Circular(queue) => {
+                let mut values = vec![
+                    queue.capacity().to_string(),
+                    queue.policy().name().to_string(),
+                    queue.head().to_string(),
+                ];
+                values.extend(queue.iter().cloned());
+                restore_command("circular_queue", values)
+            }
+            queue => restore_command("queue", queue.iter().cloned()),
+        }
+    }
+}
+
+const QUEUE_COMMANDS: &[CommandSpec] = &[
+    CommandSpec { name: "QPUSH", signature: "<value>", help: "Enqueue a value", lookup: false },
+    CommandSpec { name: "QPOP", signature: "", help: "Dequeue the front value", lookup: false },
+    CommandSpec { name: "QPEEK", signature: "", help: "Show the front value", lookup: true },
+    CommandSpec { name: "QEMPTY", signature: "", help: "Check whether the queue is empty", lookup: true },
+    CommandSpec { name: "QFULL", signature: "", help: "Check whether the queue is full", lookup: true },
+    CommandSpec { name: "QLEN", signature: "", help: "Show the queue size", lookup: true },
+    CommandSpec {
+        name: "QCAPACITY",
+        signature: "[capacity:usize|unbounded] [policy:overwrite|reject]",
+        help: "Show the capacity or switch between circular and unbounded mode",
+        lookup: false,
+    },
+    CommandSpec { name: "QPRINT", signature: "", help: "Print the queue", lookup: true },
+];
+
+// Настройки загружаются раньше элементов: от них зависит вид очереди
+const QUEUE_FILES: &[DataFile] = &[
+    DataFile { suffix: "queue_config", description: None },
+    DataFile { suffix: "queue", description: Some("queue") },
+];
+
+impl Module for QueueModule {
+    fn title(&self) -> &'static str {
+        "Queue"
+    }
+
+    fn name(&self) -> &'static str {
+        "queue"
+    }
+
+    fn commands(&self) -> &'static [CommandSpec] {
+        QUEUE_COMMANDS
+    }
+
+    fn execute(&mut self, call: &Call, ds: &mut DataStructures, out: &mut CommandResult) {
+        let (query, parts) = (call.query, call.parts);
+        match call.command {
+            "QPUSH" => {
+                let inverse = self.restore_command();
+                match self.queue.enqueue(parts[1].to_string()) {
+                    Ok(overwritten) => {
+                        out.line(format!("Enqueued \"{}\"", parts[1]));
+                        if let Some(oldest) = overwritten {
+                            out.line(format!("Overwrote oldest \"{}\"", oldest));
+                        }
+                        ds.record(query, vec![inverse]);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Failed, e),
+                }
+            }
+            "QPOP" => {
+                let inverse = self.restore_command();
+                match self.queue.dequeue() {
+                    Ok(value) => {
+                        out.line(format!("Dequeued: \"{}\"", value));
+                        out.set_value(value.clone());
+                        ds.record(query, vec![inverse]);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Empty, e),
+                }
+            }
+            "QPEEK" => {
+                match self.queue.peek() {
+                    Ok(value) => {
+                        out.line(format!("Queue front: \"{}\"", value));
+                        out.set_value(value);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Empty, e),
+                }
+            }
+            "QEMPTY" => {
+                out.line(format!("Queue is {}", if self.queue.is_empty() { "empty" } else { "not empty" }));
+                out.set_value(self.queue.is_empty());
+            }
+            "QLEN" => {
+                out.line(format!("Queue size: {}", self.queue.len()));
+                out.set_value(self.queue.len());
+            }
+            "QFULL" => {
+                out.line(format!("Queue is {}", if self.queue.is_full() { "full" } else { "not full" }));
+                out.set_value(self.queue.is_full());
+            }
+            "QCAPACITY" => {
+                if parts.len() > 1 {
+                    let policy = match (parts.get(2), &self.queue) {
+                        (Some(name), _) => OverflowPolicy::parse(name),
+                        (None, QueueMode::Circular(queue)) => Some(queue.policy()),
+                        (None, _) => Some(OverflowPolicy::Reject),
+                    };
+                    let capacity = if parts[1].eq_ignore_ascii_case("unbounded") {
+                        Some(None)
+                    } else {
+                        parts[1].parse::<usize>().ok().filter(|&capacity| capacity > 0).map(Some)
+                    };
+                    let inverse = self.restore_command();
+                    match (capacity, policy) {
+                        (Some(None), _) => {
+                            let mut queue = self.queue_backend.new_queue();
+                            for value in self.queue.iter() {
+                                queue.enqueue(value.clone()).ok();
+                            }
+                            self.queue = queue;
+                            out.line("Queue is now unbounded");
+                            out.set_value("unbounded");
+                            ds.record(query, vec![inverse]);
+                        }
+                        (Some(Some(capacity)), Some(policy)) => {
+                            if policy == OverflowPolicy::Reject && self.queue.len() > capacity {
+                                out.fail_as(ErrorKind::Failed, format!("Queue holds {} elements, more than capacity {}", self.queue.len(), capacity));
+                                return;
+                            }
+                            // При уменьшении ёмкости в режиме overwrite самые старые элементы вытесняются
+                            let mut queue = CircularQueue::new(capacity, policy);
+                            for value in self.queue.iter() {
+                                if let Ok(Some(oldest)) = queue.enqueue(value.clone()) {
+                                    out.line(format!("Overwrote oldest \"{}\"", oldest));
+                                }
+                            }
+                            self.queue = QueueMode::Circular(queue);
+                            out.line(format!("Queue capacity set to {} (policy {})", capacity, policy.name()));
+                            out.set_value(capacity);
+                            ds.record(query, vec![inverse]);
+                        }
+                        (Some(Some(_)), None) => out.fail_as(ErrorKind::BadArguments, "Policy must be \"overwrite\" or \"reject\""),
+                        (None, _) => out.fail_as(ErrorKind::BadArguments, "Capacity must be a positive number or \"unbounded\""),
+                    }
+                } else {
+                    match &self.queue {
+                        QueueMode::Circular(queue) => {
+                            out.line(format!(
+                                "Queue capacity: {} (policy {}, head {}, tail {}, {})",
+                                queue.capacity(),
+                                queue.policy().name(),
+                                queue.head(),
+                                queue.tail(),
+                                if queue.is_full() { "full" } else { "not full" }
+                            ));
+                            out.set_value(queue.capacity());
+                        }
+                        _ => {
+                            out.line("Queue capacity: unbounded");
+                            out.set_value("unbounded");
+                        }
+                    }
+                }
+            }
+            "QPRINT" => {
+                let values: Vec<&String> = self.queue.iter().collect();
+                out.line(format!("Queue: {}", values.iter().map(|value| format!("{} ", value)).collect::<String>()));
+                out.set_values(values);
+            }
+            _ => unreachable!("{} is not a queue command", call.command),
+        }
+    }
+
+    fn files(&self) -> &'static [DataFile] {
+        QUEUE_FILES
+    }
+
+    fn load(&mut self, file: &str, lines: &[String]) -> Vec<String> {
+        let mut errors = Vec::new();
+        if file == "queue_config" {
+            // Ёмкость, политика переполнения и позиция головы
+            let mut capacity = None;
+            let mut policy = OverflowPolicy::Reject;
+            let mut head = 0;
+            for line in lines {
+                let setting: Vec<&str> = line.split_whitespace().collect();
+                match setting.as_slice() {
+                    ["capacity", value] => capacity = value.parse::<usize>().ok(),
+                    ["policy", name] => policy = OverflowPolicy::parse(name).unwrap_or(policy),
+                    ["head", value] => head = value.parse::<usize>().unwrap_or(0),
+                    _ => {}
+                }
+            }
+            if let Some(capacity) = capacity.filter(|&capacity| capacity > 0) {
+                self.queue = QueueMode::Circular(CircularQueue::with_head(capacity, policy, head));
+            }
+        } else {
+            for value in lines.iter().flat_map(|line| line.split_whitespace()) {
+                if let Err(e) = self.queue.enqueue(value.to_string()) {
+                    errors.push(e);
+                }
+            }
+        }
+        errors
+    }
+
+    fn save(&self, file: &str) -> String {
+        if file == "queue_config" {
+            match &self.queue {
+                QueueMode::Circular(queue) => format!(
+                    "capacity {}\npolicy {}\nhead {}\n",
+                    queue.capacity(),
+                    queue.policy().name(),
+                    queue.head()
+                ),
+                _ => String::new(),
+            }
+        } else {
+            self.queue.iter().map(|value| format!("{} ", value)).collect()
+        }
+    }
+
+
+    fn snapshot(&self) -> Option<String> {
+        Some(self.restore_command())
+    }
+
+    fn restores(&self, target: &str) -> bool {
+        target == "queue" || target == "circular_queue"
+    }
+
+    fn restore(&mut self, target: &str, values: &[&str]) -> Result<(), String> {
+        let owned = values.iter().map(|value| value.to_string());
+        if target == "circular_queue" {
+            let (capacity, policy, head) = match values {
+                [capacity, policy, head, ..] => (
+                    capacity.parse::<usize>().ok().filter(|&capacity| capacity > 0),
+                    OverflowPolicy::parse(policy),
+                    head.parse::<usize>().ok(),
+                ),
+                _ => (None, None, None),
+            };
+            let (capacity, policy, head) = match (capacity, policy, head) {
+                (Some(capacity), Some(policy), Some(head)) => (capacity, policy, head),
+                _ => return Err("Invalid queue snapshot".to_string()),
+            };
+            let mut queue = CircularQueue::with_head(capacity, policy, head);
+            for value in owned.skip(3) {
+                queue.enqueue(value)?;
+            }
+            self.queue = QueueMode::Circular(queue);
+        } else {
+            self.queue = self.queue_backend.new_queue();
+            for value in owned {
+                self.queue.enqueue(value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct PriorityQueueModule {
+    priority_queue: PriorityQueue,
+}
+
+impl PriorityQueueModule {
+    pub fn new() -> Self {
+        PriorityQueueModule {
+            priority_queue: PriorityQueue::new(),
+        }
+    }
+
+    // Следующий номер, затем тройки "номер приоритет значение"
+    fn restore_command(&self) -> String {
+        let mut values = vec![self.priority_queue.next_seq().to_string()];
+        for entry in self.priority_queue.sorted() {
+            values.push(entry.seq.to_string());
+            values.push(entry.priority.to_string());
+            values.push(entry.value.clone());
+        }
+        restore_command("priority_queue", values)
+    }
+}
+
+const PRIORITY_QUEUE_COMMANDS: &[CommandSpec] = &[
+    CommandSpec { name: "PQPUSH", signature: "<priority:i64> <value>", help: "Enqueue a value with a priority", lookup: false },
+    CommandSpec { name: "PQPOP", signature: "", help: "Dequeue the value with the highest priority", lookup: false },
+    CommandSpec { name: "PQPEEK", signature: "", help: "Show the value with the highest priority", lookup: true },
+    CommandSpec { name: "PQCHANGE", signature: "<value> <priority:i64>", help: "Change the priority of the earliest matching value", lookup: false },
+    CommandSpec { name: "PQEMPTY", signature: "", help: "Check whether the priority queue is empty", lookup: true },
+    CommandSpec { name: "PQLEN", signature: "", help: "Show the priority queue size", lookup: true },
+    CommandSpec { name: "PQPRINT", signature: "", help: "Print the values in dequeue order", lookup: true },
+];
+
+const PRIORITY_QUEUE_FILES: &[DataFile] = &[DataFile {
+    suffix: "priority_queue",
+    description: Some("priority queue"),
+}];
+
+impl Module for PriorityQueueModule {
+    fn title(&self) -> &'static str {
+        "Priority Queue"
+    }
+
+    fn name(&self) -> &'static str {
+        "priority_queue"
+    }
+
+    fn commands(&self) -> &'static [CommandSpec] {
+        PRIORITY_QUEUE_COMMANDS
+    }
+
+    fn execute(&mut self, call: &Call, ds: &mut DataStructures, out: &mut CommandResult) {
+        let (query, parts) = (call.query, call.parts);
+        match call.command {
+            "PQPUSH" => {
+                let priority = call.number::<i64>(1);
+                let inverse = self.restore_command();
+                self.priority_queue.push(priority, parts[2].to_string());
+                out.line(format!("Enqueued \"{}\" with priority {}", parts[2], priority));
+                ds.record(query, vec![inverse]);
+            }
+            "PQPOP" => {
+                let inverse = self.restore_command();
+                match self.priority_queue.pop() {
+                    Ok(entry) => {
+                        out.line(format!("Dequeued: \"{}\" (priority {})", entry.value, entry.priority));
+                        out.set_value(entry.value.clone());
+                        ds.record(query, vec![inverse]);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Empty, e),
+                }
+            }
+            "PQPEEK" => {
+                match self.priority_queue.peek() {
+                    Ok(entry) => {
+                        out.line(format!("Priority queue front: \"{}\" (priority {})", entry.value, entry.priority));
+                        out.set_value(&entry.value);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Empty, e),
+                }
+            }
+            "PQCHANGE" => {
+                let priority = call.number::<i64>(2);
+                match self.priority_queue.change_priority(parts[1], priority) {
+                    Ok(old) => {
+                        out.line(format!("Changed priority of \"{}\" from {} to {}", parts[1], old, priority));
+                        ds.record(query, vec![format!("PQCHANGE {} {}", parts[1], old)]);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::NotFound, e),
+                }
+            }
+            "PQEMPTY" => {
+                out.line(format!("Priority queue is {}", if self.priority_queue.is_empty() { "empty" } else { "not empty" }));
+                out.set_value(self.priority_queue.is_empty());
+            }
+            "PQLEN" => {
+                out.line(format!("Priority queue size: {}", self.priority_queue.len()));
+                out.set_value(self.priority_queue.len());
+            }
+            "PQPRINT" => {
+                let values: Vec<String> = self
+                    .priority_queue
+                    .sorted()
+                    .iter()
+                    .map(|entry| format!("{}:{}", entry.priority, entry.value))
+                    .collect();
+                out.line(format!("Priority queue: {}", values.iter().map(|value| format!("{} ", value)).collect::<String>()));
+                out.set_values(values);
+            }
+            _ => unreachable!("{} is not a priority queue command", call.command),
+        }
+    }
+
+    fn files(&self) -> &'static [DataFile] {
+        PRIORITY_QUEUE_FILES
+    }
+
+    fn load(&mut self, _file: &str, lines: &[String]) -> Vec<String> {
+        let mut tokens = lines.iter().flat_map(|line| line.split_whitespace());
+        if let Some(next_seq) = tokens.next().and_then(|token| token.parse::<u64>().ok()) {
+            self.priority_queue.set_next_seq(next_seq);
+        }
+        while let (Some(seq), Some(priority), Some(value)) = (tokens.next(), tokens.next(), tokens.next()) {
+            if let (Ok(seq), Ok(priority)) = (seq.parse::<u64>(), priority.parse::<i64>()) {
+                self.priority_queue.push_with_seq(seq, priority, value.to_string());
+            }
+        }
+        Vec::new()
+    }
+
+    fn save(&self, _file: &str) -> String {
+        let mut text = format!("{}\n", self.priority_queue.next_seq());
+        for entry in self.priority_queue.sorted() {
+            text.push_str(&format!("{} {} {} ", entry.seq, entry.priority, entry.value));
+        }
+        text
+    }
+
+    fn snapshot(&self) -> Option<String> {
+        Some(self.restore_command())
+    }
+
+    fn restore(&mut self, _target: &str, values: &[&str]) -> Result<(), String> {
+        let mut values = values.iter();
+        let next_seq = values.next().and_then(|next_seq| next_seq.parse::<u64>().ok());
+        self.priority_queue = PriorityQueue::new();
+        self.priority_queue
+            .set_next_seq(next_seq.ok_or_else(|| "Invalid priority queue snapshot".to_string())?);
+        while let (Some(seq), Some(priority), Some(value)) = (values.next(), values.next(), values.next()) {
+            match (seq.parse::<u64>(), priority.parse::<i64>()) {
+                (Ok(seq), Ok(priority)) => self.priority_queue.push_with_seq(seq, priority, value.to_string()),
+                _ => return Err("Invalid priority queue snapshot".to_string()),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PriorityQueueModule, QueueBackend, QueueModule};
+    use crate::cli::OutputFormat;
+    use crate::output::ErrorKind;
+    use crate::registry::Module;
+    use crate::testing::{printed, run, structures};
+
+    #[test]
+    fn qlen_counts_elements_in_every_mode() {
+        let mut ds = structures();
+        run(&mut ds, &["QPUSH a", "QPUSH b", "QPUSH c", "QPOP"]);
+        assert_eq!(printed(&mut ds, "QLEN"), "Queue size: 2");
+        run(&mut ds, &["QCAPACITY 2 overwrite", "QPUSH d"]);
+        assert_eq!(printed(&mut ds, "QLEN"), "Queue size: 2");
+        run(&mut ds, &["QCAPACITY unbounded", "QPOP", "QPOP"]);
+        assert_eq!(printed(&mut ds, "QLEN"), "Queue size: 0");
+        run(&mut ds, &["QPOP"]);
+        assert_eq!(printed(&mut ds, "QLEN"), "Queue size: 0");
+
+        let mut ds = crate::create_data_structures(QueueBackend::TwoStacks);
+        run(&mut ds, &["QPUSH a", "QPUSH b", "QPOP", "QPUSH c"]);
+        assert_eq!(printed(&mut ds, "QLEN"), "Queue size: 2");
+    }
+
+    #[test]
+    fn qcapacity_switches_modes() {
+        let mut ds = structures();
+        assert_eq!(printed(&mut ds, "QCAPACITY"), "Queue capacity: unbounded");
+        run(&mut ds, &["QPUSH a", "QPUSH b", "QPUSH c"]);
+        let rejected = run(&mut ds, &["QCAPACITY 2 reject"]);
+        assert_eq!(rejected.kind, Some(ErrorKind::Failed));
+        assert_eq!(rejected.lines, ["Error: Queue holds 3 elements, more than capacity 2"]);
+
+        assert_eq!(
+            printed(&mut ds, "QCAPACITY 2 overwrite"),
+            "Overwrote oldest \"a\"\nQueue capacity set to 2 (policy overwrite)"
+        );
+        assert_eq!(printed(&mut ds, "QCAPACITY"), "Queue capacity: 2 (policy overwrite, head 1, tail 1, full)");
+        assert_eq!(printed(&mut ds, "QPRINT"), "Queue: b c ");
+
+        run(&mut ds, &["UNDO"]);
+        assert_eq!(printed(&mut ds, "QCAPACITY"), "Queue capacity: unbounded");
+        assert_eq!(printed(&mut ds, "QPRINT"), "Queue: a b c ");
+
+        assert_eq!(run(&mut ds, &["QCAPACITY 0"]).kind, Some(ErrorKind::BadArguments));
+        assert_eq!(run(&mut ds, &["QCAPACITY 4 drop"]).kind, Some(ErrorKind::BadArguments));
+        run(&mut ds, &["QCAPACITY 4", "QCAPACITY unbounded"]);
+        assert_eq!(printed(&mut ds, "QPRINT"), "Queue: a b c ");
+    }
+
+    #[test]
+    fn full_circular_queue_overwrites_or_rejects() {
+        let mut ds = structures();
+        run(&mut ds, &["QCAPACITY 2", "QPUSH a", "QPUSH b"]);
+        assert_eq!(printed(&mut ds, "QFULL"), "Queue is full");
+        let rejected = run(&mut ds, &["QPUSH c"]);
+        assert_eq!(rejected.kind, Some(ErrorKind::Failed));
+        assert_eq!(rejected.lines, ["Error: Queue is full (capacity 2)"]);
+
+        run(&mut ds, &["QCAPACITY 2 overwrite"]);
+        assert_eq!(printed(&mut ds, "QPUSH c"), "Enqueued \"c\"\nOverwrote oldest \"a\"");
+        run(&mut ds, &["UNDO"]);
+        assert_eq!(printed(&mut ds, "QPRINT"), "Queue: a b ");
+        assert_eq!(printed(&mut ds, "QCAPACITY"), "Queue capacity: 2 (policy overwrite, head 0, tail 0, full)");
+    }
+
+    #[test]
+    fn circular_config_is_saved_and_loaded() {
+        let mut ds = structures();
+        run(&mut ds, &["QCAPACITY 3 overwrite", "QPUSH a", "QPUSH b", "QPOP", "QPUSH c"]);
+        let module = ds.get_mut::<QueueModule>().unwrap();
+        let config = module.save("queue_config");
+        let contents = module.save("queue");
+        assert_eq!(config, "capacity 3\npolicy overwrite\nhead 1\n");
+        assert_eq!(contents, "b c ");
+
+        let mut loaded = QueueModule::new(QueueBackend::Linked);
+        let lines = |text: &str| text.lines().map(String::from).collect::<Vec<_>>();
+        assert!(loaded.load("queue_config", &lines(&config)).is_empty());
+        assert!(loaded.load("queue", &lines(&contents)).is_empty());
+        assert_eq!(loaded.save("queue_config"), config);
+        assert_eq!(loaded.restore_command(), ds.get_mut::<QueueModule>().unwrap().restore_command());
+    }
+
+    #[test]
+    fn priority_queue_commands_with_undo() {
+        let mut ds = structures();
+        run(&mut ds, &["PQPUSH 1 a", "PQPUSH 5 b", "PQPUSH 1 c"]);
+        assert_eq!(printed(&mut ds, "PQPRINT"), "Priority queue: 5:b 1:a 1:c ");
+        assert_eq!(printed(&mut ds, "PQCHANGE c 9"), "Changed priority of \"c\" from 1 to 9");
+        assert_eq!(printed(&mut ds, "PQPEEK"), "Priority queue front: \"c\" (priority 9)");
+        run(&mut ds, &["UNDO"]);
+        assert_eq!(printed(&mut ds, "PQPOP"), "Dequeued: \"b\" (priority 5)");
+        run(&mut ds, &["UNDO"]);
+        assert_eq!(printed(&mut ds, "PQPRINT"), "Priority queue: 5:b 1:a 1:c ");
+        assert_eq!(run(&mut ds, &["PQCHANGE z 1"]).kind, Some(ErrorKind::NotFound));
+        assert_eq!(run(&mut ds, &["PQPUSH high a"]).kind, Some(ErrorKind::BadArguments));
+
+        run(&mut ds, &["PQPOP", "PQPOP", "PQPOP"]);
+        assert_eq!(printed(&mut ds, "PQEMPTY"), "Priority queue is empty");
+        assert_eq!(run(&mut ds, &["PQPOP"]).kind, Some(ErrorKind::Empty));
+    }
+
+    #[test]
+    fn priority_queue_load_keeps_fifo_and_tolerates_large_numbers() {
+        let lines = |text: &str| text.lines().map(String::from).collect::<Vec<_>>();
+        let mut module = PriorityQueueModule::new();
+        let text = format!("3\n{} 1 late 0 1 early x 2 bad ", u64::MAX);
+        assert!(module.load("priority_queue", &lines(&text)).is_empty());
+        assert_eq!(module.save("priority_queue"), format!("{}\n0 1 early {} 1 late ", u64::MAX, u64::MAX));
+    }
+
+    #[test]
+    fn two_stacks_backend_behaves_like_linked_queue() {
+        let mut ds = crate::create_data_structures(QueueBackend::TwoStacks);
+        run(&mut ds, &["QPUSH a", "QPUSH b", "QPOP", "QPUSH c"]);
+        assert_eq!(printed(&mut ds, "QPEEK"), "Queue front: \"b\"");
+        assert_eq!(printed(&mut ds, "QPRINT"), "Queue: b c ");
+        run(&mut ds, &["UNDO", "UNDO"]);
+        assert_eq!(printed(&mut ds, "QPRINT"), "Queue: a b ");
+
+        // После кольцевого режима очередь снова строится на двух стеках
+        run(&mut ds, &["QCAPACITY 3", "QCAPACITY unbounded", "QPUSH d"]);
+        assert_eq!(printed(&mut ds, "QPOP"), "Dequeued: \"a\"");
+        assert_eq!(printed(&mut ds, "QPRINT"), "Queue: b d ");
+        run(&mut ds, &["QPOP", "QPOP"]);
+        assert_eq!(run(&mut ds, &["QPOP"]).kind, Some(ErrorKind::Empty));
+    }
+
+    #[test]
+    fn qcapacity_reports_capacity_as_value() {
+        let mut ds = structures();
+        let printed = run(&mut ds, &["QCAPACITY"]).render(OutputFormat::Json);
+        assert!(printed.contains(r#""value":"unbounded""#), "{}", printed);
+        let printed = run(&mut ds, &["QCAPACITY 3 overwrite"]).render(OutputFormat::Json);
+        assert!(printed.contains(r#""value":3"#), "{}", printed);
+        let printed = run(&mut ds, &["QCAPACITY"]).render(OutputFormat::Json);
+        assert!(printed.contains(r#""value":3"#), "{}", printed);
+        let printed = run(&mut ds, &["QCAPACITY unbounded"]).render(OutputFormat::Json);
+        assert!(printed.contains(r#""value":"unbounded""#), "{}", printed);
+    }
+}
@@ -0,0 +1,575 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use crate::args;
+use crate::history::History;
+use crate::output::{CommandResult, ErrorKind};
+use crate::transaction::Transaction;
+
+// Реестр команд. Каждая структура реализует Module и сама описывает
+// свои команды, справку, файлы данных и снимки для отмены.
+// Диспетчер находит модуль по имени команды и не знает о конкретных структурах.
+
+pub struct CommandSpec {
+    pub name: &'static str,
+    // Аргументы в формате args::validate
+    pub signature: &'static str,
+    pub help: &'static str,
+    // Поиск или просмотр: если искомого нет (NotFound, Empty), транзакция не прерывается
+    pub lookup: bool,
+}
+
+// Файл данных {base}_{suffix}.txt
+pub struct DataFile {
+    pub suffix: &'static str,
+    // Название для сообщений о загрузке и сохранении; None — без сообщений
+    pub description: Option<&'static str>,
+}
+
+// Команда с уже проверенными аргументами
+pub struct Call<'a> {
+    // Исходный текст запроса, записывается в историю
+    pub query: &'a str,
+    // Имя команды в верхнем регистре
+    pub command: &'a str,
+    // Слова запроса без имени экземпляра; parts[0] — имя команды
+    pub parts: &'a [&'a str],
+    // Экземпляр структуры, к которому относится команда
+    pub instance: &'a str,
+}
+
+impl Call<'_> {
+    // Числовой аргумент parts[index], тип которого уже проверен по сигнатуре
+    pub fn number<T: FromStr>(&self, index: usize) -> T {
+        match self.parts[index].parse() {
+            Ok(value) => value,
+            Err(_) => panic!("argument {} of {} was not validated", index, self.command),
+        }
+    }
+}
+
+pub trait Module: Any {
+    // Заголовок группы команд в справке
+    fn title(&self) -> &'static str;
+
+    // Имя структуры в командах _RESTORE
+    fn name(&self) -> &'static str;
+
+    fn commands(&self) -> &'static [CommandSpec];
+
+    // Вызывается только для команд из commands(). Модуль на время вызова
+    // извлекается из ds, остальные структуры и история доступны через ds.
+    fn execute(&mut self, call: &Call, ds: &mut DataStructures, out: &mut CommandResult);
+
+    // Файлы данных в порядке загрузки
+    fn files(&self) -> &'static [DataFile] {
+        &[]
+    }
+
+    // Возвращает ошибки элементов, которые не удалось загрузить
+    fn load(&mut self, _file: &str, _lines: &[String]) -> Vec<String> {
+        Vec::new()
+    }
+
+    // Содержимое файла данных
+    fn save(&self, _file: &str) -> String {
+        String::new()
+    }
+
+    // Отмена через _RESTORE <target> <values...>; target обычно совпадает с name()
+    fn restores(&self, target: &str) -> bool {
+        target == self.name()
+    }
+
+    fn restore(&mut self, target: &str, _values: &[&str]) -> Result<(), String> {
+        Err(format!("Unknown structure \"{}\"", target))
+    }
+
+    // Команда _RESTORE, возвращающая структуру в текущее состояние;
+    // None — структура не умеет восстанавливаться
+    fn snapshot(&self) -> Option<String> {
+        None
+    }
+}
+
+// Команда _RESTORE с перечисленными значениями
+pub fn restore_command<I: IntoIterator<Item = String>>(target: &str, values: I) -> String {
+    let mut command = format!("_RESTORE {}", target);
+    for value in values {
+        command.push(' ');
+        command.push_str(&value);
+    }
+    command
+}
+
+// Экземпляр, к которому относятся команды без имени экземпляра
+pub const DEFAULT_INSTANCE: &str = "default";
+
+// Создаёт пустой экземпляр структуры
+pub type Factory = Box<dyn Fn() -> Box<dyn Module>>;
+
+struct Instance {
+    name: String,
+    // None — экземпляр выполняет команду и временно извлечён
+    module: Option<Box<dyn Module>>,
+}
+
+// Вид структуры со всеми экземплярами; первый экземпляр — по умолчанию
+struct Kind {
+    // Имя структуры, как в Module::name
+    name: &'static str,
+    factory: Factory,
+    instances: Vec<Instance>,
+}
+
+impl Kind {
+    fn default_module(&self) -> Option<&dyn Module> {
+        self.instances[0].module.as_deref()
+    }
+
+    fn find(&self, name: &str) -> Option<usize> {
+        self.instances.iter().position(|instance| instance.name == name)
+    }
+
+    // Структуры без файлов данных не хранят состояния, экземпляры им не нужны
+    fn has_instances(&self) -> bool {
+        self.default_module().is_some_and(|module| !module.files().is_empty())
+    }
+}
+
+// Имя экземпляра попадает в имена файлов: буква, затем буквы, цифры, "_" и "-"
+fn valid_instance_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+// Обратная команда для именованного экземпляра: имя добавляется после команды,
+// а у _RESTORE — к имени структуры через "@", если экземпляр ещё не указан
+fn qualify(command: &str, instance: &str) -> String {
+    let mut words: Vec<String> = command.split(' ').map(|word| word.to_string()).collect();
+    if words[0] == "_RESTORE" && words.len() > 1 {
+        if !words[1].contains('@') {
+            words[1] = format!("{}@{}", words[1], instance);
+        }
+    } else {
+        words.insert(1, instance.to_string());
+    }
+    words.join(" ")
+}
+
+// Состояние всех структур и истории для отката транзакции
+pub struct Snapshot {
+    // Для каждого вида структуры: имена экземпляров и команды _RESTORE
+    instances: Vec<Vec<(String, Option<String>)>>,
+    removed: Vec<(usize, String)>,
+    history: History,
+}
+
+// Все структуры данных программы
+pub struct DataStructures {
+    kinds: Vec<Kind>,
+    // Экземпляр, выполняющий текущую команду
+    current: String,
+    // Удалённые и переименованные экземпляры: их файлы убираются при сохранении
+    removed: Vec<(usize, String)>,
+    pub history: History,
+    // Сообщения структур, выводимые вместе с результатом текущей команды
+    pub notices: Rc<RefCell<Vec<String>>>,
+    // Начатая командой BEGIN транзакция
+    pub transaction: Option<Transaction>,
+}
+
+impl DataStructures {
+    pub fn new(factories: Vec<Factory>, history: History, notices: Rc<RefCell<Vec<String>>>) -> Self {
+        let kinds = factories
+            .into_iter()
+            .map(|factory| {
+                let module = factory();
+                Kind {
+                    name: module.name(),
+                    factory,
+                    instances: vec![Instance {
+                        name: DEFAULT_INSTANCE.to_string(),
+                        module: Some(module),
+                    }],
+                }
+            })
+            .collect();
+        DataStructures {
+            kinds,
+            current: DEFAULT_INSTANCE.to_string(),
+            removed: Vec::new(),
+            history,
+            notices,
+            transaction: None,
+        }
+    }
+
+    // Экземпляры по умолчанию: по ним строится справка
+    pub fn modules(&self) -> impl Iterator<Item = &dyn Module> {
+        self.kinds.iter().filter_map(|kind| kind.default_module())
+    }
+
+    // Все экземпляры с именами, по видам структур
+    pub fn instances(&self) -> impl Iterator<Item = (&str, &dyn Module)> {
+        self.kinds.iter().flat_map(|kind| {
+            kind.instances
+                .iter()
+                .filter_map(|instance| Some((instance.name.as_str(), instance.module.as_deref()?)))
+        })
+    }
+
+    pub fn instances_mut(&mut self) -> impl Iterator<Item = (&str, &mut Box<dyn Module>)> {
+        self.kinds.iter_mut().flat_map(|kind| {
+            kind.instances
+                .iter_mut()
+                .filter_map(|instance| Some((instance.name.as_str(), instance.module.as_mut()?)))
+        })
+    }
+
+    // Экземпляры, удалённые за время работы, вместе с модулем их вида
+    pub fn removed_instances(&self) -> impl Iterator<Item = (&str, &dyn Module)> {
+        self.removed.iter().filter_map(|(index, name)| {
+            let kind = &self.kinds[*index];
+            match kind.find(name) {
+                Some(_) => None,
+                None => Some((name.as_str(), kind.default_module()?)),
+            }
+        })
+    }
+
+    // Экземпляр заданного типа с тем же именем, что и у выполняющего команду,
+    // например для команд, работающих с двумя структурами
+    pub fn get_mut<T: Module>(&mut self) -> Option<&mut T> {
+        let current = &self.current;
+        self.kinds
+            .iter_mut()
+            .flat_map(|kind| kind.instances.iter_mut())
+            .filter(|instance| instance.name == *current)
+            .find_map(|instance| (instance.module.as_mut()?.as_mut() as &mut dyn Any).downcast_mut::<T>())
+    }
+
+    // Другой экземпляр того же типа по имени, например источник для переноса элементов.
+    // Экземпляр, выполняющий команду, недоступен
+    pub fn instance_mut<T: Module>(&mut self, name: &str) -> Option<&mut T> {
+        self.kinds
+            .iter_mut()
+            .flat_map(|kind| kind.instances.iter_mut())
+            .filter(|instance| instance.name == name)
+            .find_map(|instance| (instance.module.as_mut()?.as_mut() as &mut dyn Any).downcast_mut::<T>())
+    }
+
+    pub fn find_command(&self, command: &str) -> Option<&'static CommandSpec> {
+        self.modules()
+            .flat_map(|module| module.commands())
+            .find(|spec| spec.name == command)
+    }
+
+    fn command_kind(&self, command: &str) -> Option<usize> {
+        self.kinds.iter().position(|kind| {
+            kind.default_module()
+                .is_some_and(|module| module.commands().iter().any(|spec| spec.name == command))
+        })
+    }
+
+    fn kind(&self, structure: &str) -> Result<usize, String> {
+        match self.kinds.iter().position(|kind| kind.name.eq_ignore_ascii_case(structure)) {
+            Some(index) if self.kinds[index].has_instances() => Ok(index),
+            Some(index) => Err(format!("Structure \"{}\" has no instances", self.kinds[index].name)),
+            None => Err(format!("Unknown structure \"{}\"", structure)),
+        }
+    }
+
+    // Имя экземпляра в первом аргументе команды структуры. Если аргументы подходят
+    // к сигнатуре и без имени, команда относится к экземпляру по умолчанию
+    // (SCOMPARATOR numeric задаёт порядок, даже если есть экземпляр numeric).
+    // Иначе первый аргумент считается именем, если такой экземпляр есть, а остальные
+    // аргументы подходят; в остальных случаях ошибку в аргументах сообщит проверка
+    // сигнатуры. У команд с произвольным текстом ([text...]) имени экземпляра нет.
+    pub fn select_instance<'q>(&self, spec: &CommandSpec, args: &[&'q str]) -> Option<&'q str> {
+        let kind = &self.kinds[self.command_kind(spec.name)?];
+        let (&name, rest) = args.split_first()?;
+        if !kind.has_instances()
+            || spec.signature.contains("...")
+            || args::validate(spec.name, spec.signature, args).is_ok()
+            || args::validate(spec.name, spec.signature, rest).is_err()
+        {
+            return None;
+        }
+        kind.find(name).map(|_| name)
+    }
+
+    pub fn execute(&mut self, call: &Call, out: &mut CommandResult) {
+        let Some(index) = self.command_kind(call.command) else {
+            out.fail_as(ErrorKind::UnknownCommand, format!("Unknown command: {}", call.command));
+            return;
+        };
+        let kind = &mut self.kinds[index];
+        let Some(position) = kind.find(call.instance) else {
+            out.fail_as(ErrorKind::NotFound, format!("No {} instance \"{}\"", kind.name, call.instance));
+            return;
+        };
+
+        let mut module = kind.instances[position].module.take().unwrap();
+        self.current = call.instance.to_string();
+        module.execute(call, self, out);
+        self.current = DEFAULT_INSTANCE.to_string();
+        self.kinds[index].instances[position].module = Some(module);
+    }
+
+    // Запись в историю; обратные команды именованного экземпляра получают его имя
+    pub fn record(&mut self, query: &str, inverse: Vec<String>) {
+        let inverse = if self.current == DEFAULT_INSTANCE {
+            inverse
+        } else {
+            inverse.iter().map(|command| qualify(command, &self.current)).collect()
+        };
+        self.history.record(query, inverse);
+    }
+
+    // target — имя структуры, для именованного экземпляра "структура@имя"
+    pub fn restore(&mut self, target: &str, values: &[&str]) -> Result<(), String> {
+        let (target, name) = target.split_once('@').unwrap_or((target, DEFAULT_INSTANCE));
+        let kind = self
+            .kinds
+            .iter_mut()
+            .find(|kind| kind.default_module().is_some_and(|module| module.restores(target)))
+            .ok_or_else(|| format!("Unknown structure \"{}\"", target))?;
+        let position = kind
+            .find(name)
+            .ok_or_else(|| format!("No {} instance \"{}\"", kind.name, name))?;
+        match kind.instances[position].module.as_mut() {
+            Some(module) => module.restore(target, values),
+            None => Err(format!("Instance \"{}\" of {} is busy", name, kind.name)),
+        }
+    }
+
+    // Имена экземпляров каждой структуры, хранящей состояние
+    pub fn instance_names(&self) -> impl Iterator<Item = (&'static str, Vec<&str>)> {
+        self.kinds.iter().filter(|kind| kind.has_instances()).map(|kind| {
+            let names = kind.instances.iter().map(|instance| instance.name.as_str()).collect();
+            (kind.name, names)
+        })
+    }
+
+    pub fn create_instance(&mut self, structure: &str, name: &str) -> Result<(), String> {
+        let index = self.kind(structure)?;
+        let kind = &mut self.kinds[index];
+        if !valid_instance_name(name) {
+            return Err(format!("Invalid instance name \"{}\"", name));
+        }
+        if kind.find(name).is_some() {
+            return Err(format!("Instance \"{}\" of {} already exists", name, kind.name));
+        }
+        let module = (kind.factory)();
+        kind.instances.push(Instance {
+            name: name.to_string(),
+            module: Some(module),
+        });
+        Ok(())
+    }
+
+    // Возвращает снимок удалённого экземпляра для отмены
+    pub fn drop_instance(&mut self, structure: &str, name: &str) -> Result<Option<String>, String> {
+        let index = self.kind(structure)?;
+        let position = self.named_instance(index, name)?;
+        let instance = self.kinds[index].instances.remove(position);
+        self.removed.push((index, instance.name));
+        Ok(instance.module.and_then(|module| module.snapshot()).map(|snapshot| qualify(&snapshot, name)))
+    }
+
+    pub fn rename_instance(&mut self, structure: &str, name: &str, new_name: &str) -> Result<(), String> {
+        let index = self.kind(structure)?;
+        let position = self.named_instance(index, name)?;
+        let kind = &mut self.kinds[index];
+        if !valid_instance_name(new_name) || new_name == DEFAULT_INSTANCE {
+            return Err(format!("Invalid instance name \"{}\"", new_name));
+        }
+        if kind.find(new_name).is_some() {
+            return Err(format!("Instance \"{}\" of {} already exists", new_name, kind.name));
+        }
+        kind.instances[position].name = new_name.to_string();
+        self.removed.push((index, name.to_string()));
+        Ok(())
+    }
+
+    // Экземпляр по умолчанию нельзя удалить или переименовать
+    fn named_instance(&self, index: usize, name: &str) -> Result<usize, String> {
+        let kind = &self.kinds[index];
+        if name == DEFAULT_INSTANCE {
+            return Err(format!("The default {} instance cannot be changed", kind.name));
+        }
+        kind.find(name)
+            .ok_or_else(|| format!("No {} instance \"{}\"", kind.name, name))
+    }
+
+    pub fn snapshot(&self) -> Result<Snapshot, String> {
+        let mut instances = Vec::new();
+        for kind in &self.kinds {
+            let mut snapshots = Vec::new();
+            for instance in &kind.instances {
+                let module = instance
+                    .module
+                    .as_ref()
+                    .ok_or_else(|| format!("Instance \"{}\" of {} is busy", instance.name, kind.name))?;
+                let snapshot = module.snapshot();
+                if snapshot.is_none() && kind.has_instances() {
+                    return Err(format!("Structure \"{}\" cannot be rolled back", kind.name));
+                }
+                snapshots.push((instance.name.clone(), snapshot));
+            }
+            instances.push(snapshots);
+        }
+        Ok(Snapshot {
+            instances,
+            removed: self.removed.clone(),
+            history: self.history.clone(),
+        })
+    }
+
+    // Экземпляры создаются заново и восстанавливаются командами _RESTORE из снимка
+    pub fn rollback(&mut self, snapshot: Snapshot) -> Result<(), String> {
+        let mut errors = Vec::new();
+        for (kind, snapshots) in self.kinds.iter_mut().zip(snapshot.instances) {
+            kind.instances.clear();
+            for (name, restore) in snapshots {
+                let mut module = (kind.factory)();
+                if let Some(restore) = restore {
+                    let words: Vec<&str> = restore.split_whitespace().collect();
+                    let restored = match words.get(1) {
+                        Some(target) => module.restore(target, &words[2..]),
+                        None => Err(format!("Invalid snapshot of {} \"{}\"", kind.name, name)),
+                    };
+                    if let Err(e) = restored {
+                        errors.push(e);
+                    }
+                }
+                kind.instances.push(Instance {
+                    name,
+                    module: Some(module),
+                });
+            }
+        }
+        self.removed = snapshot.removed;
+        self.history = snapshot.history;
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::{qualify, restore_command, valid_instance_name, Call, CommandSpec, DataFile, DataStructures, Module};
+    use crate::history::History;
+    use crate::output::{CommandResult, ErrorKind};
+    use crate::testing::{printed, run, structures};
+
+    #[test]
+    fn restore_and_qualified_commands() {
+        assert_eq!(restore_command("stack", Vec::new()), "_RESTORE stack");
+        assert_eq!(restore_command("stack", vec!["a".to_string(), "b".to_string()]), "_RESTORE stack a b");
+        assert_eq!(qualify("SPOP", "other"), "SPOP other");
+        assert_eq!(qualify("MDEL 2", "other"), "MDEL other 2");
+        assert_eq!(qualify("_RESTORE stack a", "other"), "_RESTORE stack@other a");
+        assert_eq!(qualify("_RESTORE stack@x a", "other"), "_RESTORE stack@x a");
+    }
+
+    #[test]
+    fn instance_names_are_file_safe() {
+        for name in ["a", "list_2", "Big-one"] {
+            assert!(valid_instance_name(name), "{}", name);
+        }
+        for name in ["", "2nd", "_x", "a.b", "a/b", "ü"] {
+            assert!(!valid_instance_name(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn commands_are_found_in_modules() {
+        let ds = structures();
+        assert_eq!(ds.find_command("SPUSH").map(|spec| spec.signature), Some("<value>"));
+        assert!(ds.find_command("QPUSH").is_some());
+        assert!(ds.find_command("spush").is_none());
+        assert!(ds.find_command("UNDO").is_none());
+        assert!(ds.modules().all(|module| !module.commands().is_empty()));
+    }
+
+    #[test]
+    fn dispatch_help_and_unknown_commands() {
+        let mut ds = structures();
+        assert_eq!(printed(&mut ds, "spush a"), "Pushed \"a\" to stack");
+        assert_eq!(printed(&mut ds, "HELP spush"), "SPUSH <value> - Push a value");
+        assert_eq!(printed(&mut ds, "HELP UNDO"), "UNDO - Undo the last change");
+
+        let help = printed(&mut ds, "HELP");
+        assert!(help.starts_with("Available commands:\nArray: MPUSH_BACK"), "{}", help);
+        assert!(help.contains("\nStack: SPUSH, SPOP"), "{}", help);
+        assert!(help.ends_with("Other: HELP"), "{}", help);
+
+        let unknown = run(&mut ds, &["HELP NOPE"]);
+        assert_eq!(unknown.kind, Some(ErrorKind::UnknownCommand));
+        assert_eq!(unknown.lines, ["Error: Unknown command: NOPE"]);
+
+        let unknown = run(&mut ds, &["nope 1"]);
+        assert_eq!(unknown.kind, Some(ErrorKind::UnknownCommand));
+        assert_eq!(unknown.lines[0], "Unknown command: NOPE");
+        assert_eq!(unknown.lines[1..].join("\n"), help);
+
+        let empty = run(&mut ds, &["   "]);
+        assert!(empty.lines.is_empty() && empty.kind.is_none());
+    }
+
+    #[test]
+    fn arguments_are_validated_before_dispatch() {
+        let mut ds = structures();
+        let result = run(&mut ds, &["SPUSH"]);
+        assert_eq!(result.kind, Some(ErrorKind::BadArguments));
+        assert_eq!(result.lines, ["Error: SPUSH expects <value>"]);
+        assert_eq!(run(&mut ds, &["SLEN extra words"]).kind, Some(ErrorKind::BadArguments));
+        assert_eq!(run(&mut ds, &["SPOP"]).kind, Some(ErrorKind::Empty));
+    }
+
+    // Модуль, снимок которого не содержит имени структуры
+    struct Broken;
+
+    impl Module for Broken {
+        fn title(&self) -> &'static str {
+            "Broken"
+        }
+
+        fn name(&self) -> &'static str {
+            "broken"
+        }
+
+        fn commands(&self) -> &'static [CommandSpec] {
+            &[]
+        }
+
+        fn execute(&mut self, _call: &Call, _ds: &mut DataStructures, _out: &mut CommandResult) {}
+
+        fn files(&self) -> &'static [DataFile] {
+            &[DataFile { suffix: "broken", description: None }]
+        }
+
+        fn snapshot(&self) -> Option<String> {
+            Some("_RESTORE".to_string())
+        }
+    }
+
+    #[test]
+    fn rollback_rejects_short_snapshots() {
+        let mut ds = DataStructures::new(vec![Box::new(|| Box::new(Broken))], History::new(5), Rc::new(RefCell::new(Vec::new())));
+        let snapshot = ds.snapshot().unwrap();
+        assert_eq!(ds.rollback(snapshot), Err("Invalid snapshot of broken \"default\"".to_string()));
+        assert_eq!(ds.modules().count(), 1);
+    }
+}
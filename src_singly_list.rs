@@ -19,6 +19,14 @@ impl SinglyList {
         }
     }
 
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
     pub fn add_head(&mut self, value: String) {
         let mut new_node = Box::new(Node {
             data: value,
@@ -55,31 +63,31 @@ impl SinglyList {
         self.size += 1;
     }
 
-    pub fn remove_head(&mut self) -> Result<(), String> {
-        if self.head.is_none() {
-            return Err("List is empty".to_string());
-        }
+    pub fn remove_head(&mut self) -> Result<String, String> {
+        self.pop_head().ok_or_else(|| "List is empty".to_string())
+    }
 
-        self.head = self.head.as_mut().and_then(|node| node.next.take());
+    fn pop_head(&mut self) -> Option<String> {
+        let mut old_head = self.head.take()?;
+        self.head = old_head.next.take();
 
         if self.head.is_none() {
             self.tail = std::ptr::null_mut();
         }
 
         self.size -= 1;
-        Ok(())
+        Some(old_head.data)
     }
 
-    pub fn remove_tail(&mut self) -> Result<(), String> {
+    pub fn remove_tail(&mut self) -> Result<String, String> {
         if self.head.is_none() {
             return Err("List is empty".to_string());
         }
 
         if self.head.as_ref().unwrap().next.is_none() {
-            self.head = None;
             self.tail = std::ptr::null_mut();
             self.size -= 1;
-            return Ok(());
+            return Ok(self.head.take().unwrap().data);
         }
 
         let mut current = self.head.as_mut().unwrap();
@@ -87,33 +95,111 @@ impl SinglyList {
             current = current.next.as_mut().unwrap();
         }
 
-        current.next = None;
-        self.tail = current as *mut Node;
+        let removed = current.next.take().unwrap();
+        self.tail = &mut **current as *mut Node;
         self.size -= 1;
-        Ok(())
+        Ok(removed.data)
     }
 
-    pub fn remove_value(&mut self, value: &str) -> Result<(), String> {
+    pub fn remove_value(&mut self, value: &str) -> Result<String, String> {
         if self.head.is_none() {
             return Err("List is empty".to_string());
         }
 
-        if self.head.as_ref().unwrap().data == value {
-            return self.remove_head();
+        let mut cursor = self.cursor_front_mut();
+        while let Some(current) = cursor.current() {
+            if current.as_str() == value {
+                return Ok(cursor.remove_current().unwrap());
+            }
+            cursor.move_next();
         }
 
-        let mut current = self.head.as_mut().unwrap();
-        while let Some(ref mut next_node) = current.next {
-            if next_node.data == value {
-                let removed = next_node.next.take();
-                current.next = removed;
-                self.size -= 1;
-                return Ok(());
+        Err("Value not found".to_string())
+    }
+
+    pub fn remove_all(&mut self, value: &str) -> usize {
+        self.retain(|current| current != value)
+    }
+
+    // Оставляет только элементы, для которых keep вернул true; возвращает число удалённых
+    pub fn retain<F: FnMut(&String) -> bool>(&mut self, mut keep: F) -> usize {
+        self.drain_filter(|current| !keep(current)).len()
+    }
+
+    // Извлекает элементы, подходящие под условие, сохраняя их порядок
+    pub fn drain_filter<F: FnMut(&String) -> bool>(&mut self, mut filter: F) -> Vec<String> {
+        let mut removed = Vec::new();
+        let mut cursor = self.cursor_front_mut();
+        while let Some(current) = cursor.current() {
+            if filter(current) {
+                removed.extend(cursor.remove_current());
+            } else {
+                cursor.move_next();
             }
-            current = current.next.as_mut().unwrap();
         }
+        removed
+    }
 
-        Err("Value not found".to_string())
+    // При index == len вставляет в конец
+    pub fn insert(&mut self, index: usize, value: String) -> Result<(), String> {
+        if index > self.size {
+            return Err("Index out of range".to_string());
+        }
+
+        let mut cursor = self.cursor_front_mut();
+        for _ in 0..index {
+            cursor.move_next();
+        }
+        cursor.insert_before(value);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, index: usize) -> Result<String, String> {
+        if index >= self.size {
+            return Err("Index out of range".to_string());
+        }
+
+        let mut cursor = self.cursor_front_mut();
+        for _ in 0..index {
+            cursor.move_next();
+        }
+        Ok(cursor.remove_current().unwrap())
+    }
+
+    // Переносит все элементы other в конец списка за O(1)
+    pub fn append(&mut self, other: &mut SinglyList) {
+        if let Some((head, tail, count)) = other.take_chain() {
+            let last = self.tail;
+            self.link_after(last, head, tail, count);
+        }
+    }
+
+    // Отрезает элементы начиная с index в новый список
+    pub fn split_off(&mut self, index: usize) -> Result<SinglyList, String> {
+        if index > self.size {
+            return Err("Index out of range".to_string());
+        }
+
+        let mut rest = SinglyList::new();
+        if index == 0 {
+            std::mem::swap(self, &mut rest);
+            return Ok(rest);
+        }
+        if index == self.size {
+            return Ok(rest);
+        }
+
+        let mut current = self.head.as_deref_mut().unwrap();
+        for _ in 1..index {
+            current = current.next.as_deref_mut().unwrap();
+        }
+
+        rest.head = current.next.take();
+        rest.tail = self.tail;
+        rest.size = self.size - index;
+        self.tail = current;
+        self.size = index;
+        Ok(rest)
     }
 
     pub fn find(&self, value: &str) -> Option<usize> {
@@ -131,19 +217,85 @@ impl SinglyList {
         None
     }
 
-    pub fn print(&self) {
-        let mut current = self.head.as_ref();
-        while let Some(node) = current {
-            print!("{} -> ", node.data);
-            current = node.next.as_ref();
+    pub fn iter(&self) -> SinglyListIter<'_> {
+        SinglyListIter {
+            current: self.head.as_deref(),
         }
-        println!("nullptr");
     }
 
-    pub fn iter(&self) -> SinglyListIter {
-        SinglyListIter {
+    pub fn iter_mut(&mut self) -> SinglyListIterMut<'_> {
+        SinglyListIterMut {
+            current: self.head.as_deref_mut(),
+        }
+    }
+
+    pub fn cursor_front(&self) -> Cursor<'_> {
+        Cursor {
+            list: self,
             current: self.head.as_deref(),
+            index: 0,
+        }
+    }
+
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_> {
+        let current = Self::node_ptr(&mut self.head);
+        CursorMut {
+            prev: if current.is_null() {
+                self.tail
+            } else {
+                std::ptr::null_mut()
+            },
+            current,
+            index: 0,
+            list: self,
+        }
+    }
+
+    fn node_ptr(link: &mut Option<Box<Node>>) -> *mut Node {
+        link.as_deref_mut()
+            .map_or(std::ptr::null_mut(), |node| node as *mut Node)
+    }
+
+    // Вставка цепочки head..=tail после узла prev (в начало, если prev нулевой)
+    fn link_after(&mut self, prev: *mut Node, head: Box<Node>, tail: *mut Node, count: usize) {
+        unsafe {
+            if prev.is_null() {
+                (*tail).next = self.head.take();
+                self.head = Some(head);
+            } else {
+                (*tail).next = (*prev).next.take();
+                (*prev).next = Some(head);
+            }
         }
+
+        if self.tail == prev {
+            self.tail = tail;
+        }
+        self.size += count;
+    }
+
+    fn link_value_after(&mut self, prev: *mut Node, value: String) -> *mut Node {
+        let mut new_node = Box::new(Node {
+            data: value,
+            next: None,
+        });
+        let raw_node: *mut _ = &mut *new_node;
+        self.link_after(prev, new_node, raw_node, 1);
+        raw_node
+    }
+
+    // Забирает все узлы списка: (голова, хвост, количество)
+    fn take_chain(&mut self) -> Option<(Box<Node>, *mut Node, usize)> {
+        let head = self.head.take()?;
+        let tail = std::mem::replace(&mut self.tail, std::ptr::null_mut());
+        let count = std::mem::replace(&mut self.size, 0);
+        Some((head, tail, count))
+    }
+}
+
+impl Default for SinglyList {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -160,4 +312,474 @@ impl<'a> Iterator for SinglyListIter<'a> {
             &node.data
         })
     }
-}
\ No newline at end of file
+}
+
+pub struct SinglyListIterMut<'a> {
+    current: Option<&'a mut Node>,
+}
+
+impl<'a> Iterator for SinglyListIterMut<'a> {
+    type Item = &'a mut String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.current.take().map(|node| {
+            self.current = node.next.as_deref_mut();
+            &mut node.data
+        })
+    }
+}
+
+pub struct SinglyListIntoIter {
+    list: SinglyList,
+}
+
+impl Iterator for SinglyListIntoIter {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_head()
+    }
+}
+
+impl IntoIterator for SinglyList {
+    type Item = String;
+    type IntoIter = SinglyListIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        SinglyListIntoIter { list: self }
+    }
+}
+
+impl<'a> IntoIterator for &'a SinglyList {
+    type Item = &'a String;
+    type IntoIter = SinglyListIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut SinglyList {
+    type Item = &'a mut String;
+    type IntoIter = SinglyListIterMut<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl FromIterator<String> for SinglyList {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        let mut list = SinglyList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl Extend<String> for SinglyList {
+    fn extend<I: IntoIterator<Item = String>>(&mut self, iter: I) {
+        for value in iter {
+            self.add_tail(value);
+        }
+    }
+}
+
+// Курсор только для чтения. За последним элементом находится "призрачная"
+// позиция, из которой move_next возвращает курсор в начало списка.
+pub struct Cursor<'a> {
+    list: &'a SinglyList,
+    current: Option<&'a Node>,
+    index: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        match self.current.take() {
+            Some(node) => {
+                self.current = node.next.as_deref();
+                self.index += 1;
+            }
+            None => {
+                self.current = self.list.head.as_deref();
+                self.index = 0;
+            }
+        }
+    }
+
+    pub fn current(&self) -> Option<&'a String> {
+        self.current.map(|node| &node.data)
+    }
+
+    pub fn peek_next(&self) -> Option<&'a String> {
+        match self.current {
+            Some(node) => node.next.as_deref().map(|next| &next.data),
+            None => self.list.head.as_deref().map(|head| &head.data),
+        }
+    }
+}
+
+// Курсор для редактирования на месте. prev указывает на узел перед текущим;
+// в призрачной позиции это хвост списка.
+pub struct CursorMut<'a> {
+    list: &'a mut SinglyList,
+    prev: *mut Node,
+    current: *mut Node,
+    index: usize,
+}
+
+impl<'a> CursorMut<'a> {
+    pub fn index(&self) -> Option<usize> {
+        if self.current.is_null() {
+            None
+        } else {
+            Some(self.index)
+        }
+    }
+
+    pub fn move_next(&mut self) {
+        if self.current.is_null() {
+            self.prev = std::ptr::null_mut();
+            self.current = SinglyList::node_ptr(&mut self.list.head);
+            self.index = 0;
+        } else {
+            self.prev = self.current;
+            self.current = unsafe { SinglyList::node_ptr(&mut (*self.current).next) };
+            self.index += 1;
+        }
+
+        if self.current.is_null() {
+            self.prev = self.list.tail;
+        }
+    }
+
+    pub fn current(&mut self) -> Option<&mut String> {
+        unsafe { self.current.as_mut().map(|node| &mut node.data) }
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut String> {
+        let next = match unsafe { self.current.as_mut() } {
+            Some(node) => node.next.as_deref_mut(),
+            None => self.list.head.as_deref_mut(),
+        };
+        next.map(|node| &mut node.data)
+    }
+
+    // В призрачной позиции вставляет в начало списка
+    pub fn insert_after(&mut self, value: String) {
+        self.list.link_value_after(self.current, value);
+        if self.current.is_null() {
+            self.prev = self.list.tail;
+        }
+    }
+
+    // В призрачной позиции вставляет в конец списка
+    pub fn insert_before(&mut self, value: String) {
+        self.prev = self.list.link_value_after(self.prev, value);
+        if !self.current.is_null() {
+            self.index += 1;
+        }
+    }
+
+    // Удаляет текущий элемент, курсор переходит на следующий
+    pub fn remove_current(&mut self) -> Option<String> {
+        if self.current.is_null() {
+            return None;
+        }
+
+        let mut removed = unsafe {
+            match self.prev.as_mut() {
+                Some(prev) => prev.next.take(),
+                None => self.list.head.take(),
+            }
+        }
+        .expect("cursor points to a linked node");
+
+        let mut next = removed.next.take();
+        self.current = SinglyList::node_ptr(&mut next);
+        unsafe {
+            match self.prev.as_mut() {
+                Some(prev) => prev.next = next,
+                None => self.list.head = next,
+            }
+        }
+
+        if std::ptr::eq(self.list.tail, &*removed) {
+            self.list.tail = self.prev;
+        }
+        self.list.size -= 1;
+        Some(removed.data)
+    }
+
+    // Переносит все элементы other после текущего за O(1)
+    pub fn splice_after(&mut self, mut other: SinglyList) {
+        if let Some((head, tail, count)) = other.take_chain() {
+            self.list.link_after(self.current, head, tail, count);
+            if self.current.is_null() {
+                self.prev = self.list.tail;
+            }
+        }
+    }
+
+    // Переносит все элементы other перед текущим за O(1)
+    pub fn splice_before(&mut self, mut other: SinglyList) {
+        if let Some((head, tail, count)) = other.take_chain() {
+            self.list.link_after(self.prev, head, tail, count);
+            self.prev = tail;
+            if !self.current.is_null() {
+                self.index += count;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(values: &[&str]) -> SinglyList {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn values(list: &SinglyList) -> Vec<&str> {
+        list.iter().map(String::as_str).collect()
+    }
+
+    fn text(value: Option<&String>) -> Option<&str> {
+        value.map(String::as_str)
+    }
+
+    // Хвост должен указывать на последний узел: add_tail дописывает в конец
+    fn assert_tail(list: &mut SinglyList) {
+        let mut expected: Vec<String> = list.iter().cloned().collect();
+        expected.push("#".to_string());
+        list.add_tail("#".to_string());
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), expected);
+        assert_eq!(list.len(), expected.len());
+        assert_eq!(list.remove_tail(), Ok("#".to_string()));
+    }
+
+    #[test]
+    fn cursor_walks_through_ghost_position() {
+        let list = list(&["a", "b", "c"]);
+        let mut cursor = list.cursor_front();
+        assert_eq!(text(cursor.current()), Some("a"));
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(text(cursor.peek_next()), Some("b"));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.peek_next(), None);
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(text(cursor.peek_next()), Some("a"));
+        cursor.move_next();
+        assert_eq!(text(cursor.current()), Some("a"));
+        assert_eq!(cursor.index(), Some(0));
+
+        let empty = SinglyList::new();
+        let mut cursor = empty.cursor_front();
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), None);
+    }
+
+    #[test]
+    fn cursor_mut_inserts_and_removes() {
+        let mut list = list(&["a", "c"]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.insert_before("b".to_string());
+        assert_eq!(cursor.index(), Some(2));
+        cursor.insert_after("d".to_string());
+        assert_eq!(cursor.remove_current(), Some("c".to_string()));
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current().map(|v| v.as_str()), Some("d"));
+        cursor.move_next();
+        cursor.insert_before("e".to_string());
+        cursor.insert_after("z".to_string());
+        assert_eq!(cursor.remove_current(), None);
+        assert_eq!(cursor.peek_next().map(|v| v.as_str()), Some("z"));
+        assert_eq!(values(&list), ["z", "a", "b", "d", "e"]);
+        assert_tail(&mut list);
+    }
+
+    #[test]
+    fn cursor_mut_removes_tail_and_head() {
+        let mut list = list(&["a", "b"]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some("b".to_string()));
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some("a".to_string()));
+        assert_eq!(cursor.index(), None);
+        assert!(list.is_empty());
+        assert_tail(&mut list);
+    }
+
+    #[test]
+    fn cursor_mut_splices_lists() {
+        let mut list = list(&["a", "d"]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.splice_before(["b", "c"].iter().map(|v| v.to_string()).collect());
+        assert_eq!(cursor.index(), Some(3));
+        cursor.splice_after(["e"].iter().map(|v| v.to_string()).collect());
+        cursor.splice_after(SinglyList::new());
+        cursor.move_next();
+        cursor.move_next();
+        cursor.splice_after(["0"].iter().map(|v| v.to_string()).collect());
+        cursor.splice_before(["f"].iter().map(|v| v.to_string()).collect());
+        assert_eq!(values(&list), ["0", "a", "b", "c", "d", "e", "f"]);
+        assert_eq!(list.len(), 7);
+        assert_tail(&mut list);
+    }
+
+    #[test]
+    fn cursor_mut_edits_in_place() {
+        let mut list = list(&["a", "b"]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.current().unwrap().push('!');
+        cursor.peek_next().unwrap().push('?');
+        assert_eq!(values(&list), ["a!", "b?"]);
+    }
+
+    #[test]
+    fn cursors_and_iterators_interleave() {
+        let mut list = list(&["a", "b", "c"]);
+        {
+            let mut first = list.cursor_front();
+            let second = list.cursor_front();
+            let mut iter = list.iter();
+            assert_eq!(text(iter.next()), text(first.current()));
+            first.move_next();
+            assert_eq!(text(iter.next()), text(second.peek_next()));
+            assert_eq!(text(iter.next()), text(first.peek_next()));
+            assert_eq!(iter.next(), None);
+        }
+        {
+            let mut cursor = list.cursor_front_mut();
+            cursor.move_next();
+            assert_eq!(cursor.remove_current(), Some("b".to_string()));
+            cursor.current().unwrap().push('1');
+        }
+        for value in list.iter_mut() {
+            value.push('2');
+        }
+        let cursor = list.cursor_front();
+        let mut iter = list.iter();
+        assert_eq!(text(iter.next()), text(cursor.current()));
+        assert_eq!(text(iter.next()), Some("c12"));
+        assert_eq!(values(&list), ["a2", "c12"]);
+        assert_tail(&mut list);
+    }
+
+    #[test]
+    fn iterators_follow_list_order() {
+        let mut list = list(&["a", "b", "c"]);
+        assert_eq!(values(&list), ["a", "b", "c"]);
+        for value in &mut list {
+            value.push('!');
+        }
+        list.iter_mut().for_each(|value| value.push('?'));
+        assert_eq!((&list).into_iter().count(), 3);
+        let owned: Vec<String> = list.into_iter().collect();
+        assert_eq!(owned, ["a!?", "b!?", "c!?"]);
+    }
+
+    #[test]
+    fn collect_and_extend_append_to_tail() {
+        let mut list: SinglyList = ["a", "b"].iter().map(|v| v.to_string()).collect();
+        list.extend(["c".to_string()]);
+        assert_eq!(values(&list), ["a", "b", "c"]);
+        assert_eq!(list.len(), 3);
+        assert_tail(&mut list);
+        let round_trip: SinglyList = list.into_iter().collect();
+        assert_eq!(values(&round_trip), ["a", "b", "c"]);
+
+        let empty: SinglyList = std::iter::empty().collect();
+        assert!(empty.is_empty());
+        assert_eq!(empty.iter().next(), None);
+    }
+
+    #[test]
+    fn remove_all_counts_removed() {
+        let mut list = list(&["x", "a", "x", "b", "x"]);
+        assert_eq!(list.remove_all("x"), 3);
+        assert_eq!(values(&list), ["a", "b"]);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.remove_all("x"), 0);
+        assert_tail(&mut list);
+    }
+
+    #[test]
+    fn retain_keeps_matching_elements() {
+        let mut list = list(&["1", "2", "3", "4"]);
+        assert_eq!(list.retain(|value| value != "4" && value != "1"), 2);
+        assert_eq!(values(&list), ["2", "3"]);
+        assert_tail(&mut list);
+        assert_eq!(list.retain(|_| false), 2);
+        assert!(list.is_empty());
+        assert_tail(&mut list);
+    }
+
+    #[test]
+    fn drain_filter_returns_removed_in_order() {
+        let mut list = list(&["a1", "b", "a2", "c", "a3"]);
+        assert_eq!(list.drain_filter(|value| value.starts_with('a')), ["a1", "a2", "a3"]);
+        assert_eq!(values(&list), ["b", "c"]);
+        assert_tail(&mut list);
+        assert!(list.drain_filter(|_| false).is_empty());
+
+        let mut empty = SinglyList::new();
+        assert!(empty.drain_filter(|_| true).is_empty());
+    }
+
+    #[test]
+    fn append_moves_all_elements() {
+        let mut first = list(&["a", "b"]);
+        let mut second = list(&["c", "d"]);
+        first.append(&mut second);
+        assert_eq!(values(&first), ["a", "b", "c", "d"]);
+        assert_eq!(first.len(), 4);
+        assert!(second.is_empty());
+        assert_tail(&mut first);
+        assert_tail(&mut second);
+
+        let mut empty = SinglyList::new();
+        empty.append(&mut first);
+        first.append(&mut SinglyList::new());
+        assert_eq!(values(&empty), ["a", "b", "c", "d"]);
+        assert!(first.is_empty());
+        assert_tail(&mut empty);
+    }
+
+    #[test]
+    fn split_off_keeps_both_halves_consistent() {
+        let mut first = list(&["a", "b", "c", "d"]);
+        let mut rest = first.split_off(1).unwrap();
+        assert_eq!(values(&first), ["a"]);
+        assert_eq!(values(&rest), ["b", "c", "d"]);
+        assert_eq!((first.len(), rest.len()), (1, 3));
+        assert_tail(&mut first);
+        assert_tail(&mut rest);
+
+        let mut tail = rest.split_off(3).unwrap();
+        assert!(tail.is_empty());
+        assert_eq!(rest.len(), 3);
+        assert_tail(&mut tail);
+        let mut all = rest.split_off(0).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(values(&all), ["b", "c", "d"]);
+        assert_tail(&mut all);
+        assert_tail(&mut rest);
+
+        assert_eq!(first.split_off(2).err(), Some("Index out of range".to_string()));
+        assert_eq!(values(&first), ["a"]);
+    }
+}
@@ -1,17 +1,60 @@
-// Стек на основе вектора
+use std::cmp::Ordering;
+
+// Стек на основе вектора. Без ограничения capacity только резервирует память,
+// в ограниченном режиме push при заполнении возвращает ошибку.
 pub struct Stack {
     data: Vec<String>,
+    limit: Option<usize>,
 }
 
 impl Stack {
     pub fn new(capacity: usize) -> Self {
         Stack {
             data: Vec::with_capacity(capacity),
+            limit: None,
+        }
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        self.limit
+    }
+
+    // None снимает ограничение
+    pub fn set_capacity(&mut self, capacity: Option<usize>) -> Result<(), String> {
+        if let Some(limit) = capacity {
+            if self.data.len() > limit {
+                return Err(format!(
+                    "Stack holds {} elements, more than capacity {}",
+                    self.data.len(),
+                    limit
+                ));
+            }
         }
+        self.limit = capacity;
+        Ok(())
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.limit.is_some_and(|limit| self.data.len() >= limit)
     }
 
-    pub fn push(&mut self, value: String) {
+    pub fn push(&mut self, value: String) -> Result<(), String> {
+        if let Some(limit) = self.limit {
+            if self.data.len() >= limit {
+                return Err(format!("Stack overflow: capacity {} reached", limit));
+            }
+        }
         self.data.push(value);
+        Ok(())
+    }
+
+    // Кладёт элементы по порядку до первого не поместившегося и возвращает ошибку
+    // переполнения; уже положенные элементы остаются в стеке
+    pub fn try_extend<I: IntoIterator<Item = String>>(&mut self, iter: I) -> Result<(), String> {
+        for value in iter {
+            self.push(value)?;
+        }
+        Ok(())
     }
 
     pub fn pop(&mut self) -> Result<String, String> {
@@ -22,11 +65,384 @@ impl Stack {
         self.data.last().ok_or_else(|| "Stack is empty".to_string())
     }
 
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
     pub fn is_empty(&self) -> bool {
         self.data.is_empty()
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = &String> {
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &String> {
+        self.data.iter().rev()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut String> {
+        self.data.iter_mut().rev()
+    }
+}
+
+// Все итераторы стека идут от вершины ко дну
+impl IntoIterator for Stack {
+    type Item = String;
+    type IntoIter = std::iter::Rev<std::vec::IntoIter<String>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter().rev()
+    }
+}
+
+impl<'a> IntoIterator for &'a Stack {
+    type Item = &'a String;
+    type IntoIter = std::iter::Rev<std::slice::Iter<'a, String>>;
+
+    fn into_iter(self) -> Self::IntoIter {
         self.data.iter().rev()
     }
-}
\ No newline at end of file
+}
+
+impl<'a> IntoIterator for &'a mut Stack {
+    type Item = &'a mut String;
+    type IntoIter = std::iter::Rev<std::slice::IterMut<'a, String>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter_mut().rev()
+    }
+}
+
+// FromIterator и Extend кладут элементы по порядку, как последовательность push:
+// последний элемент оказывается на вершине. Итераторы идут от вершины ко дну,
+// поэтому копия стека собирается из stack.iter().rev()
+impl FromIterator<String> for Stack {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        let mut stack = Stack::new(0);
+        stack.extend(iter);
+        stack
+    }
+}
+
+// Переполнение ограниченного стека — ошибка вызывающего: проверить его позволяет try_extend
+impl Extend<String> for Stack {
+    fn extend<I: IntoIterator<Item = String>>(&mut self, iter: I) {
+        if let Err(e) = self.try_extend(iter) {
+            panic!("{}; use Stack::try_extend to handle overflow", e);
+        }
+    }
+}
+
+// Порядок сравнения элементов для MinMaxStack
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Comparator {
+    Lexicographic,
+    // Числа идут раньше нечисловых строк, нечисловые сравниваются лексикографически
+    Numeric,
+}
+
+impl Comparator {
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        match self {
+            Comparator::Lexicographic => a.cmp(b),
+            Comparator::Numeric => match (a.parse::<f64>(), b.parse::<f64>()) {
+                (Ok(x), Ok(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => a.cmp(b),
+            },
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Comparator::Lexicographic => "lexicographic",
+            Comparator::Numeric => "numeric",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "lex" | "lexicographic" => Some(Comparator::Lexicographic),
+            "num" | "numeric" => Some(Comparator::Numeric),
+            _ => None,
+        }
+    }
+}
+
+// Стек с отслеживанием минимума и максимума за O(1).
+// mins/maxs хранят индексы текущего минимума/максимума после каждого push.
+pub struct MinMaxStack {
+    stack: Stack,
+    mins: Vec<usize>,
+    maxs: Vec<usize>,
+    comparator: Comparator,
+}
+
+impl MinMaxStack {
+    pub fn new(capacity: usize, comparator: Comparator) -> Self {
+        MinMaxStack {
+            stack: Stack::new(capacity),
+            mins: Vec::with_capacity(capacity),
+            maxs: Vec::with_capacity(capacity),
+            comparator,
+        }
+    }
+
+    pub fn comparator(&self) -> Comparator {
+        self.comparator
+    }
+
+    // Смена компаратора пересчитывает минимумы и максимумы за O(n)
+    pub fn set_comparator(&mut self, comparator: Comparator) {
+        self.comparator = comparator;
+        self.mins.clear();
+        self.maxs.clear();
+        for index in 0..self.stack.data.len() {
+            self.track(index);
+        }
+    }
+
+    fn track(&mut self, index: usize) {
+        let data = &self.stack.data;
+        let value = &data[index];
+
+        let min = match self.mins.last() {
+            Some(&min) if self.comparator.compare(value, &data[min]) != Ordering::Less => min,
+            _ => index,
+        };
+        let max = match self.maxs.last() {
+            Some(&max) if self.comparator.compare(value, &data[max]) != Ordering::Greater => max,
+            _ => index,
+        };
+
+        self.mins.push(min);
+        self.maxs.push(max);
+    }
+
+    pub fn push(&mut self, value: String) -> Result<(), String> {
+        self.stack.push(value)?;
+        self.track(self.stack.data.len() - 1);
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Result<String, String> {
+        let value = self.stack.pop()?;
+        self.mins.pop();
+        self.maxs.pop();
+        Ok(value)
+    }
+
+    pub fn peek(&self) -> Result<&String, String> {
+        self.stack.peek()
+    }
+
+    pub fn min(&self) -> Result<&String, String> {
+        self.mins
+            .last()
+            .map(|&index| &self.stack.data[index])
+            .ok_or_else(|| "Stack is empty".to_string())
+    }
+
+    pub fn max(&self) -> Result<&String, String> {
+        self.maxs
+            .last()
+            .map(|&index| &self.stack.data[index])
+            .ok_or_else(|| "Stack is empty".to_string())
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        self.stack.capacity()
+    }
+
+    pub fn set_capacity(&mut self, capacity: Option<usize>) -> Result<(), String> {
+        self.stack.set_capacity(capacity)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.stack.is_full()
+    }
+
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &String> {
+        self.stack.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stack(values: &[&str]) -> Stack {
+        let mut stack = Stack::new(values.len());
+        for value in values {
+            stack.push(value.to_string()).unwrap();
+        }
+        stack
+    }
+
+    fn values<'a>(iter: impl Iterator<Item = &'a String>) -> Vec<&'a str> {
+        iter.map(String::as_str).collect()
+    }
+
+    #[test]
+    fn iterators_go_from_top_to_bottom() {
+        let mut stack = stack(&["a", "b", "c"]);
+        assert_eq!(values(stack.iter()), ["c", "b", "a"]);
+        assert_eq!(values(stack.iter().rev()), ["a", "b", "c"]);
+        assert_eq!(values((&stack).into_iter()), ["c", "b", "a"]);
+        for value in &mut stack {
+            value.push('!');
+        }
+        assert_eq!(stack.iter_mut().next().map(|v| v.as_str()), Some("c!"));
+        assert_eq!(stack.into_iter().collect::<Vec<_>>(), ["c!", "b!", "a!"]);
+    }
+
+    #[test]
+    fn collect_and_extend_push_in_order() {
+        let collected: Stack = ["a", "b", "c"].iter().map(|value| value.to_string()).collect();
+        let mut extended = Stack::new(0);
+        extended.extend(["a", "b", "c"].iter().map(|value| value.to_string()));
+        assert_eq!(collected.peek(), Ok(&"c".to_string()));
+        assert_eq!(values(collected.iter()), values(extended.iter()));
+        assert_eq!(collected.capacity(), None);
+
+        let copy: Stack = collected.iter().rev().cloned().collect();
+        assert_eq!(values(copy.iter()), ["c", "b", "a"]);
+        let reversed: Stack = collected.into_iter().collect();
+        assert_eq!(values(reversed.iter()), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn extend_pushes_in_order() {
+        let mut stack = stack(&["a"]);
+        stack.extend(["b".to_string(), "c".to_string()]);
+        assert_eq!(stack.pop(), Ok("c".to_string()));
+        assert_eq!(stack.len(), 2);
+    }
+
+    #[test]
+    fn empty_stack_iterates_nothing() {
+        let stack: Stack = std::iter::empty().collect();
+        assert!(stack.is_empty());
+        assert_eq!(stack.iter().next(), None);
+        assert_eq!(stack.into_iter().next(), None);
+    }
+
+    fn min_max(stack: &MinMaxStack) -> (&str, &str) {
+        (stack.min().unwrap().as_str(), stack.max().unwrap().as_str())
+    }
+
+    #[test]
+    fn min_max_follow_push_and_pop() {
+        let mut stack = MinMaxStack::new(4, Comparator::Lexicographic);
+        assert_eq!(stack.min(), Err("Stack is empty".to_string()));
+        assert_eq!(stack.max(), Err("Stack is empty".to_string()));
+        for value in ["m", "c", "x", "c", "a"] {
+            stack.push(value.to_string()).unwrap();
+        }
+        assert_eq!(min_max(&stack), ("a", "x"));
+        stack.pop().unwrap();
+        assert_eq!(min_max(&stack), ("c", "x"));
+        stack.pop().unwrap();
+        assert_eq!(min_max(&stack), ("c", "x"));
+        stack.pop().unwrap();
+        assert_eq!(min_max(&stack), ("c", "m"));
+        stack.pop().unwrap();
+        assert_eq!(min_max(&stack), ("m", "m"));
+        stack.pop().unwrap();
+        assert!(stack.min().is_err());
+        assert_eq!(stack.pop(), Err("Stack is empty".to_string()));
+    }
+
+    #[test]
+    fn numeric_comparator_orders_numbers_first() {
+        let mut stack = MinMaxStack::new(4, Comparator::Numeric);
+        for value in ["10", "9", "abc", "-1.5"] {
+            stack.push(value.to_string()).unwrap();
+        }
+        assert_eq!(min_max(&stack), ("-1.5", "abc"));
+        stack.set_comparator(Comparator::Lexicographic);
+        assert_eq!(min_max(&stack), ("-1.5", "abc"));
+        stack.pop().unwrap();
+        stack.pop().unwrap();
+        assert_eq!(min_max(&stack), ("10", "9"));
+        stack.set_comparator(Comparator::Numeric);
+        assert_eq!(min_max(&stack), ("9", "10"));
+    }
+
+    #[test]
+    fn rejected_push_keeps_tracking() {
+        let mut stack = MinMaxStack::new(2, Comparator::Lexicographic);
+        stack.set_capacity(Some(1)).unwrap();
+        stack.push("b".to_string()).unwrap();
+        assert!(stack.is_full());
+        assert_eq!(stack.push("a".to_string()), Err("Stack overflow: capacity 1 reached".to_string()));
+        assert_eq!(min_max(&stack), ("b", "b"));
+        assert_eq!(stack.len(), 1);
+    }
+
+    #[test]
+    fn comparator_names_parse() {
+        assert_eq!(Comparator::parse("NUM"), Some(Comparator::Numeric));
+        assert_eq!(Comparator::parse("lexicographic"), Some(Comparator::Lexicographic));
+        assert_eq!(Comparator::parse("other"), None);
+        assert_eq!(Comparator::Numeric.name(), "numeric");
+    }
+
+    #[test]
+    fn bounded_push_reports_overflow() {
+        let mut stack = stack(&["a"]);
+        assert_eq!(stack.capacity(), None);
+        stack.set_capacity(Some(2)).unwrap();
+        assert!(!stack.is_full());
+        stack.push("b".to_string()).unwrap();
+        assert!(stack.is_full());
+        assert_eq!(stack.push("c".to_string()), Err("Stack overflow: capacity 2 reached".to_string()));
+        assert_eq!(values(stack.iter()), ["b", "a"]);
+        stack.set_capacity(None).unwrap();
+        stack.push("c".to_string()).unwrap();
+        assert_eq!(stack.len(), 3);
+    }
+
+    #[test]
+    fn capacity_below_size_is_rejected() {
+        let mut stack = stack(&["a", "b"]);
+        assert_eq!(
+            stack.set_capacity(Some(1)),
+            Err("Stack holds 2 elements, more than capacity 1".to_string())
+        );
+        assert_eq!(stack.capacity(), None);
+        stack.set_capacity(Some(0)).unwrap_err();
+        stack.pop().unwrap();
+        stack.pop().unwrap();
+        stack.set_capacity(Some(0)).unwrap();
+        assert!(stack.is_full());
+        assert!(stack.push("a".to_string()).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Stack overflow: capacity 2 reached; use Stack::try_extend to handle overflow")]
+    fn extend_panics_at_capacity() {
+        let mut stack = stack(&["a"]);
+        stack.set_capacity(Some(2)).unwrap();
+        stack.extend(["b".to_string(), "c".to_string()]);
+    }
+
+    #[test]
+    fn try_extend_reports_overflow() {
+        let mut stack = stack(&["a"]);
+        stack.set_capacity(Some(3)).unwrap();
+        assert_eq!(stack.try_extend(["b".to_string()]), Ok(()));
+        assert_eq!(
+            stack.try_extend(["c".to_string(), "d".to_string()]),
+            Err("Stack overflow: capacity 3 reached".to_string())
+        );
+        assert_eq!(values(stack.iter()), ["c", "b", "a"]);
+        assert_eq!(Stack::new(0).try_extend(std::iter::empty()), Ok(()));
+    }
+}
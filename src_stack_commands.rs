@@ -0,0 +1,364 @@
+use crate::balance;
+use crate::output::{CommandResult, ErrorKind};
+use crate::registry::{restore_command, Call, CommandSpec, DataFile, DataStructures, Module};
+use crate::stack::{Comparator, MinMaxStack};
+
+// Команды стека с минимумом и максимумом и проверка баланса скобок
+pub struct StackModule {
+    stack: MinMaxStack,
+}
+
+impl StackModule {
+    pub fn new() -> Self {
+        StackModule {
+            stack: MinMaxStack::new(10, Comparator::Lexicographic),
+        }
+    }
+
+    // Порядок сравнения, предельный размер, затем значения от дна к вершине
+    fn restore_command(&self) -> String {
+        let mut values = vec![
+            self.stack.comparator().name().to_string(),
+            self.stack.capacity().map_or("unbounded".to_string(), |limit| limit.to_string()),
+        ];
+        let stack: Vec<&String> = self.stack.iter().collect();
+        values.extend(stack.into_iter().rev().cloned());
+        restore_command("stack", values)
+    }
+}
+
+const COMMANDS: &[CommandSpec] = &[
+    CommandSpec { name: "SPUSH", signature: "<value>", help: "Push a value", lookup: false },
+    CommandSpec { name: "SPOP", signature: "", help: "Pop the top value", lookup: false },
+    CommandSpec { name: "SPEEK", signature: "", help: "Show the top value", lookup: true },
+    CommandSpec { name: "SEMPTY", signature: "", help: "Check whether the stack is empty", lookup: true },
+    CommandSpec { name: "SLEN", signature: "", help: "Show the stack size", lookup: true },
+    CommandSpec { name: "SMIN", signature: "", help: "Show the minimum in O(1)", lookup: true },
+    CommandSpec { name: "SMAX", signature: "", help: "Show the maximum in O(1)", lookup: true },
+    CommandSpec {
+        name: "SCOMPARATOR",
+        signature: "[comparator:lex|num|lexicographic|numeric]",
+        help: "Show or set the comparison order",
+        lookup: false,
+    },
+    CommandSpec { name: "SCAPACITY", signature: "[capacity:usize|unbounded]", help: "Show or set the size limit", lookup: false },
+    CommandSpec { name: "SBALANCED", signature: "[text...]", help: "Check brackets and XML tags for balance", lookup: true },
+    CommandSpec { name: "SPRINT", signature: "", help: "Print the stack", lookup: true },
+];
+
+// Настройки загружаются раньше элементов, чтобы действовал предельный размер
+const FILES: &[DataFile] = &[
+    DataFile { suffix: "stack_config", description: None },
+    DataFile { suffix: "stack", description: Some("stack") },
+];
+
+impl Module for StackModule {
+    fn title(&self) -> &'static str {
+        "Stack"
+    }
+
+    fn name(&self) -> &'static str {
+        "stack"
+    }
+
+    fn commands(&self) -> &'static [CommandSpec] {
+        COMMANDS
+    }
+
+    fn execute(&mut self, call: &Call, ds: &mut DataStructures, out: &mut CommandResult) {
+        let (query, parts) = (call.query, call.parts);
+        match call.command {
+            "SPUSH" => {
+                match self.stack.push(parts[1].to_string()) {
+                    Ok(_) => {
+                        out.line(format!("Pushed \"{}\" to stack", parts[1]));
+                        ds.record(query, vec!["SPOP".to_string()]);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Failed, e),
+                }
+            }
+            "SPOP" => {
+                match self.stack.pop() {
+                    Ok(value) => {
+                        out.line(format!("Popped from stack: \"{}\"", value));
+                        out.set_value(value.clone());
+                        ds.record(query, vec![format!("SPUSH {}", value)]);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Empty, e),
+                }
+            }
+            "SPEEK" => {
+                match self.stack.peek() {
+                    Ok(value) => {
+                        out.line(format!("Stack top: \"{}\"", value));
+                        out.set_value(value);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Empty, e),
+                }
+            }
+            "SEMPTY" => {
+                out.line(format!("Stack is {}", if self.stack.is_empty() { "empty" } else { "not empty" }));
+                out.set_value(self.stack.is_empty());
+            }
+            "SLEN" => {
+                out.line(format!("Stack size: {}", self.stack.len()));
+                out.set_value(self.stack.len());
+            }
+            "SMIN" => {
+                match self.stack.min() {
+                    Ok(value) => {
+                        out.line(format!("Stack min: \"{}\"", value));
+                        out.set_value(value);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Empty, e),
+                }
+            }
+            "SMAX" => {
+                match self.stack.max() {
+                    Ok(value) => {
+                        out.line(format!("Stack max: \"{}\"", value));
+                        out.set_value(value);
+                    }
+                    Err(e) => out.fail_as(ErrorKind::Empty, e),
+                }
+            }
+            "SCOMPARATOR" => {
+                if parts.len() > 1 {
+                    match Comparator::parse(parts[1]) {
+                        Some(comparator) => {
+                            let inverse = format!("SCOMPARATOR {}", self.stack.comparator().name());
+                            self.stack.set_comparator(comparator);
+                            ds.record(query, vec![inverse]);
+                            out.line(format!("Stack comparator set to {}", comparator.name()));
+                            out.set_value(comparator.name());
+                        }
+                        None => out.fail_as(ErrorKind::BadArguments, "Unknown comparator (expected lex or num)"),
+                    }
+                } else {
+                    out.line(format!("Stack comparator: {}", self.stack.comparator().name()));
+                    out.set_value(self.stack.comparator().name());
+                }
+            }
+            "SCAPACITY" => {
+                if parts.len() > 1 {
+                    let capacity = if parts[1].eq_ignore_ascii_case("unbounded") {
+                        Some(None)
+                    } else {
+                        parts[1].parse::<usize>().ok().map(Some)
+                    };
+                    let inverse = match self.stack.capacity() {
+                        Some(limit) => format!("SCAPACITY {}", limit),
+                        None => "SCAPACITY unbounded".to_string(),
+                    };
+                    match capacity {
+                        Some(capacity) => match self.stack.set_capacity(capacity) {
+                            Ok(_) => {
+                                ds.record(query, vec![inverse]);
+                                match capacity {
+                                    Some(limit) => {
+                                        out.line(format!("Stack capacity set to {}", limit));
+                                        out.set_value(limit);
+                                    }
+                                    None => {
+                                        out.line("Stack is now unbounded");
+                                        out.set_value("unbounded");
+                                    }
+                                }
+                            }
+                            Err(e) => out.fail_as(ErrorKind::Failed, e),
+                        },
+                        None => out.fail_as(ErrorKind::BadArguments, "Capacity must be a number or \"unbounded\""),
+                    }
+                } else {
+                    match self.stack.capacity() {
+                        Some(limit) => {
+                            out.line(format!("Stack capacity: {} ({})", limit, if self.stack.is_full() { "full" } else { "not full" }));
+                            out.set_value(limit);
+                        }
+                        None => {
+                            out.line("Stack capacity: unbounded");
+                            out.set_value("unbounded");
+                        }
+                    }
+                }
+            }
+            "SBALANCED" => {
+                // Текст берётся из запроса целиком, чтобы позиции совпадали с исходными
+                let text = query.trim_start()[parts[0].len()..].trim();
+                let text = text
+                    .strip_prefix('"')
+                    .and_then(|text| text.strip_suffix('"'))
+                    .unwrap_or(text);
+                match balance::check_balanced(text) {
+                    Ok(_) => {
+                        out.line("Text is balanced");
+                        out.set_value(true);
+                    }
+                    Err(e) => {
+                        out.line(format!("Not balanced: {}", e));
+                        out.set_value(false);
+                    }
+                }
+            }
+            "SPRINT" => {
+                let values: Vec<&String> = self.stack.iter().collect();
+                out.line(format!("Stack: {}", values.iter().map(|value| format!("{} ", value)).collect::<String>()));
+                out.set_values(values);
+            }
+            _ => unreachable!("{} is not a stack command", call.command),
+        }
+    }
+
+    fn files(&self) -> &'static [DataFile] {
+        FILES
+    }
+
+    fn load(&mut self, file: &str, lines: &[String]) -> Vec<String> {
+        let mut errors = Vec::new();
+        if file == "stack_config" {
+            // Строки вида "ключ значение"
+            for line in lines {
+                let setting: Vec<&str> = line.split_whitespace().collect();
+                match setting.as_slice() {
+                    ["comparator", name] => {
+                        if let Some(comparator) = Comparator::parse(name) {
+                            self.stack.set_comparator(comparator);
+                        }
+                    }
+                    ["capacity", limit] => {
+                        if let Ok(limit) = limit.parse::<usize>() {
+                            self.stack.set_capacity(Some(limit)).ok();
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        } else {
+            for value in lines.iter().flat_map(|line| line.split_whitespace()) {
+                if let Err(e) = self.stack.push(value.to_string()) {
+                    errors.push(e);
+                }
+            }
+        }
+        errors
+    }
+
+    fn save(&self, file: &str) -> String {
+        if file == "stack_config" {
+            let mut config = format!("comparator {}\n", self.stack.comparator().name());
+            if let Some(limit) = self.stack.capacity() {
+                config.push_str(&format!("capacity {}\n", limit));
+            }
+            config
+        } else {
+            // От дна к вершине, чтобы при загрузке порядок сохранился
+            let values: Vec<&String> = self.stack.iter().collect();
+            values.iter().rev().map(|value| format!("{} ", value)).collect()
+        }
+    }
+
+    fn snapshot(&self) -> Option<String> {
+        Some(self.restore_command())
+    }
+
+    fn restore(&mut self, _target: &str, values: &[&str]) -> Result<(), String> {
+        let (comparator, capacity) = match values {
+            [comparator, capacity, ..] => (
+                Comparator::parse(comparator),
+                match *capacity {
+                    "unbounded" => Some(None),
+                    limit => limit.parse::<usize>().ok().map(Some),
+                },
+            ),
+            _ => (None, None),
+        };
+        let (Some(comparator), Some(capacity)) = (comparator, capacity) else {
+            return Err("Invalid stack snapshot".to_string());
+        };
+        self.stack = MinMaxStack::new(10, comparator);
+        self.stack.set_capacity(capacity)?;
+        for value in &values[2..] {
+            self.stack.push(value.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StackModule;
+    use crate::output::ErrorKind;
+    use crate::registry::Module;
+    use crate::testing::{printed, run, structures};
+
+    #[test]
+    fn slen_follows_push_and_pop() {
+        let mut ds = structures();
+        run(&mut ds, &["SPUSH a", "SPUSH b", "SPOP", "SPUSH c"]);
+        assert_eq!(printed(&mut ds, "SLEN"), "Stack size: 2");
+    }
+
+    #[test]
+    fn min_max_commands_use_comparator() {
+        let mut ds = structures();
+        assert_eq!(run(&mut ds, &["SMIN"]).kind, Some(ErrorKind::Empty));
+        run(&mut ds, &["SPUSH 10", "SPUSH 9", "SPUSH 100"]);
+        assert_eq!(printed(&mut ds, "SMIN"), "Stack min: \"10\"");
+        assert_eq!(printed(&mut ds, "SMAX"), "Stack max: \"9\"");
+        run(&mut ds, &["SCOMPARATOR num"]);
+        assert_eq!(printed(&mut ds, "SMIN"), "Stack min: \"9\"");
+        assert_eq!(printed(&mut ds, "SMAX"), "Stack max: \"100\"");
+        run(&mut ds, &["UNDO"]);
+        assert_eq!(printed(&mut ds, "SCOMPARATOR"), "Stack comparator: lexicographic");
+        assert_eq!(run(&mut ds, &["SCOMPARATOR 42"]).kind, Some(ErrorKind::BadArguments));
+    }
+
+    #[test]
+    fn bounded_stack_rejects_overflow() {
+        let mut ds = structures();
+        run(&mut ds, &["SCAPACITY 2", "SPUSH a", "SPUSH b"]);
+        assert_eq!(printed(&mut ds, "SCAPACITY"), "Stack capacity: 2 (full)");
+        let out = run(&mut ds, &["SPUSH c"]);
+        assert_eq!(out.kind, Some(ErrorKind::Failed));
+        assert_eq!(out.error.as_deref(), Some("Stack overflow: capacity 2 reached"));
+        assert_eq!(printed(&mut ds, "SLEN"), "Stack size: 2");
+        let out = run(&mut ds, &["SCAPACITY 1"]);
+        assert_eq!(out.error.as_deref(), Some("Stack holds 2 elements, more than capacity 1"));
+        run(&mut ds, &["SCAPACITY unbounded", "SPUSH c"]);
+        assert_eq!(printed(&mut ds, "SLEN"), "Stack size: 3");
+        run(&mut ds, &["UNDO", "UNDO"]);
+        assert_eq!(printed(&mut ds, "SCAPACITY"), "Stack capacity: 2 (full)");
+    }
+
+    #[test]
+    fn capacity_is_saved_and_loaded() {
+        let mut ds = structures();
+        run(&mut ds, &["SCOMPARATOR num", "SCAPACITY 3", "SPUSH a", "SPUSH b"]);
+        let module = ds.get_mut::<StackModule>().unwrap();
+        let config = module.save("stack_config");
+        let values = module.save("stack");
+        assert_eq!(config, "comparator numeric\ncapacity 3\n");
+        assert_eq!(values, "a b ");
+
+        let mut loaded = StackModule::new();
+        let config: Vec<String> = config.lines().map(|line| line.to_string()).collect();
+        assert!(loaded.load("stack_config", &config).is_empty());
+        assert!(loaded.load("stack", &[values]).is_empty());
+        assert_eq!(loaded.save("stack_config"), "comparator numeric\ncapacity 3\n");
+        assert_eq!(loaded.save("stack"), "a b ");
+        // Лишние элементы файла не помещаются в стек
+        assert_eq!(loaded.load("stack", &["c d".to_string()]), ["Stack overflow: capacity 3 reached"]);
+        assert_eq!(loaded.save("stack"), "a b c ");
+    }
+
+    #[test]
+    fn sbalanced_reports_position_and_expected_closer() {
+        let mut ds = structures();
+        assert_eq!(printed(&mut ds, "SBALANCED \"<a>(x)</a>\""), "Text is balanced");
+        assert_eq!(
+            printed(&mut ds, "SBALANCED \"{[}\""),
+            "Not balanced: Mismatch at position 2: expected \"]\" (opened at position 1) but found \"}\""
+        );
+        assert_eq!(run(&mut ds, &["SBALANCED (("]).exit_code(), 0);
+    }
+}
@@ -0,0 +1,145 @@
+use crate::registry::Snapshot;
+
+// Транзакция: команды между BEGIN и COMMIT применяются вместе.
+// Откат восстанавливает снимок всех структур и истории, сделанный при BEGIN.
+pub enum Transaction {
+    // Состояние до BEGIN
+    Active(Snapshot),
+    // После ошибки изменения уже откачены, команды пропускаются до COMMIT или ROLLBACK
+    Aborted,
+}
+
+impl Transaction {
+    pub fn is_aborted(&self) -> bool {
+        matches!(self, Transaction::Aborted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::ErrorKind;
+    use crate::registry::DataStructures;
+    use crate::testing::{printed, run, structures};
+
+    // Снимки всех экземпляров всех структур
+    fn state(ds: &DataStructures) -> Vec<(String, Option<String>)> {
+        ds.instances().map(|(name, module)| (name.to_string(), module.snapshot())).collect()
+    }
+
+    const SETUP: &[&str] = &[
+        "MPUSH_BACK a",
+        "FPUSH_TAIL b",
+        "LPUSH_TAIL c",
+        "SPUSH 10",
+        "SPUSH 9",
+        "SCOMPARATOR numeric",
+        "SCAPACITY 4",
+        "QCAPACITY 3 overwrite",
+        "QPUSH d",
+        "QPUSH e",
+        "QPOP",
+        "PQPUSH 2 f",
+        "TINSERT g",
+        "CPUT k v",
+        "DPUSH_FRONT h",
+        "CREATE STACK extra",
+        "SPUSH extra x",
+    ];
+
+    const CHANGES: &[&str] = &[
+        "MPUSH_BACK z",
+        "FDEL_HEAD",
+        "LPUSH_HEAD z",
+        "SPOP",
+        "SCOMPARATOR lexicographic",
+        "SCAPACITY unbounded",
+        "QCAPACITY 5 reject",
+        "QPUSH z",
+        "PQPOP",
+        "TINSERT z",
+        "CPUT k2 v2",
+        "DPOP_BACK",
+        "DROP STACK extra",
+        "CREATE QUEUE fresh",
+        "QPUSH fresh z",
+    ];
+
+    #[test]
+    fn rollback_restores_every_module() {
+        let mut ds = structures();
+        run(&mut ds, SETUP);
+        let before = state(&ds);
+        let history = ds.history.to_lines();
+
+        run(&mut ds, &["BEGIN"]);
+        run(&mut ds, CHANGES);
+        assert_ne!(state(&ds), before);
+        assert_eq!(printed(&mut ds, "ROLLBACK"), "Transaction rolled back");
+
+        assert_eq!(state(&ds), before);
+        assert_eq!(ds.history.to_lines(), history);
+        assert_eq!(printed(&mut ds, "SCOMPARATOR"), "Stack comparator: numeric");
+        assert_eq!(printed(&mut ds, "SCAPACITY"), "Stack capacity: 4 (not full)");
+        assert_eq!(printed(&mut ds, "QCAPACITY"), "Queue capacity: 3 (policy overwrite, head 1, tail 2, not full)");
+        assert_eq!(printed(&mut ds, "SLEN extra"), "Stack size: 1");
+    }
+
+    #[test]
+    fn failing_command_aborts_transaction() {
+        let mut ds = structures();
+        run(&mut ds, SETUP);
+        let before = state(&ds);
+
+        run(&mut ds, &["BEGIN"]);
+        run(&mut ds, CHANGES);
+        let failed = run(&mut ds, &["MDEL 99"]);
+        assert_eq!(failed.kind, Some(ErrorKind::NotFound));
+        // Изменения уже откачены, следующие команды пропускаются
+        assert_eq!(state(&ds), before);
+        let skipped = run(&mut ds, &["SPUSH y"]);
+        assert_eq!(skipped.kind, Some(ErrorKind::Failed));
+        assert_eq!(state(&ds), before);
+
+        assert_eq!(printed(&mut ds, "ROLLBACK"), "Transaction rolled back");
+        assert_eq!(state(&ds), before);
+        assert_eq!(printed(&mut ds, "SCOMPARATOR"), "Stack comparator: numeric");
+        assert_eq!(printed(&mut ds, "SCAPACITY"), "Stack capacity: 4 (not full)");
+        assert_eq!(printed(&mut ds, "QCAPACITY"), "Queue capacity: 3 (policy overwrite, head 1, tail 2, not full)");
+    }
+
+    #[test]
+    fn commit_keeps_changes_and_control_errors() {
+        let mut ds = structures();
+        assert_eq!(run(&mut ds, &["COMMIT"]).kind, Some(ErrorKind::Failed));
+        assert_eq!(run(&mut ds, &["ROLLBACK"]).kind, Some(ErrorKind::Failed));
+        run(&mut ds, &["BEGIN", "SPUSH a"]);
+        assert_eq!(run(&mut ds, &["BEGIN"]).kind, Some(ErrorKind::Failed));
+        assert_eq!(printed(&mut ds, "COMMIT"), "Transaction committed");
+        assert_eq!(printed(&mut ds, "SLEN"), "Stack size: 1");
+
+        run(&mut ds, &["BEGIN", "SPOP", "SPOP"]);
+        let commit = run(&mut ds, &["COMMIT"]);
+        assert_eq!(commit.lines, ["Error: Transaction was aborted, changes rolled back"]);
+        assert_eq!(printed(&mut ds, "SLEN"), "Stack size: 1");
+    }
+
+    #[test]
+    fn missed_lookups_do_not_abort() {
+        let mut ds = structures();
+        run(&mut ds, &["BEGIN", "FPUSH_HEAD x", "CPUT k v"]);
+        for query in ["FSEARCH nope", "TSEARCH nope", "CGET nope", "SPEEK", "DGET 3"] {
+            let result = run(&mut ds, &[query]);
+            assert!(!result.lines.iter().any(|line| line.starts_with("Transaction aborted")), "{}", query);
+        }
+        run(&mut ds, &["FPUSH_HEAD y"]);
+        assert_eq!(printed(&mut ds, "COMMIT"), "Transaction committed");
+        assert_eq!(printed(&mut ds, "FPRINT"), "Singly List: y -> x -> nullptr");
+
+        // Неудачное изменение и ошибка в аргументах прерывают транзакцию
+        for query in ["SPOP", "CDEL nope", "FSEARCH"] {
+            run(&mut ds, &["BEGIN", "FPUSH_HEAD z", query]);
+            assert_eq!(run(&mut ds, &["COMMIT"]).kind, Some(ErrorKind::Failed), "{}", query);
+            assert_eq!(printed(&mut ds, "FPRINT"), "Singly List: y -> x -> nullptr");
+        }
+    }
+}
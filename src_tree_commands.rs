@@ -0,0 +1,123 @@
+use crate::full_binary_tree::FullBinaryTree;
+use crate::output::{CommandResult, ErrorKind};
+use crate::registry::{restore_command, Call, CommandSpec, DataFile, DataStructures, Module};
+
+// Команды полного двоичного дерева
+pub struct TreeModule {
+    tree: FullBinaryTree,
+}
+
+impl TreeModule {
+    pub fn new() -> Self {
+        TreeModule {
+            tree: FullBinaryTree::new(),
+        }
+    }
+
+    fn restore_command(&self) -> String {
+        restore_command("tree", self.tree.iter().cloned())
+    }
+}
+
+const COMMANDS: &[CommandSpec] = &[
+    CommandSpec { name: "TINSERT", signature: "<value>", help: "Insert a value", lookup: false },
+    CommandSpec { name: "TSEARCH", signature: "<value>", help: "Check whether a value is in the tree", lookup: true },
+    CommandSpec { name: "TISFULL", signature: "", help: "Check whether the tree is full", lookup: true },
+    CommandSpec { name: "TSIZE", signature: "", help: "Show the number of nodes", lookup: true },
+    CommandSpec { name: "TPRINT", signature: "", help: "Print the tree", lookup: true },
+];
+
+const FILES: &[DataFile] = &[DataFile { suffix: "tree", description: Some("tree") }];
+
+impl Module for TreeModule {
+    fn title(&self) -> &'static str {
+        "Tree"
+    }
+
+    fn name(&self) -> &'static str {
+        "tree"
+    }
+
+    fn commands(&self) -> &'static [CommandSpec] {
+        COMMANDS
+    }
+
+    fn execute(&mut self, call: &Call, ds: &mut DataStructures, out: &mut CommandResult) {
+        let (query, parts) = (call.query, call.parts);
+        match call.command {
+            "TINSERT" => {
+                let inverse = self.restore_command();
+                self.tree.insert(parts[1].to_string());
+                out.line(format!("Inserted \"{}\" into tree", parts[1]));
+                ds.record(query, vec![inverse]);
+            }
+            "TSEARCH" => {
+                let found = self.tree.search(parts[1]);
+                out.line(format!("Value \"{}\" {} in tree", parts[1], if found { "found" } else { "not found" }));
+                out.set_value(found);
+                if !found {
+                    out.set_error(ErrorKind::NotFound, "Value not found");
+                }
+            }
+            "TISFULL" => {
+                out.line(format!("Tree is {}", if self.tree.is_full() { "full" } else { "not full" }));
+                out.set_value(self.tree.is_full());
+            }
+            "TSIZE" => {
+                out.line(format!("Tree size: {}", self.tree.len()));
+                out.set_value(self.tree.len());
+            }
+            "TPRINT" => {
+                if self.tree.is_empty() {
+                    out.line("Дерево пустое");
+                } else {
+                    out.line("Структура дерева:");
+                    out.lines.extend(self.tree.structure_lines());
+                    let values = self.tree.collect_inorder();
+                    out.line(format!("Симметричный обход (inorder): {}", values.iter().map(|value| format!("{} ", value)).collect::<String>()));
+                    out.set_values(values);
+                }
+            }
+            _ => unreachable!("{} is not a tree command", call.command),
+        }
+    }
+
+    fn files(&self) -> &'static [DataFile] {
+        FILES
+    }
+
+    fn load(&mut self, _file: &str, lines: &[String]) -> Vec<String> {
+        for value in lines.iter().flat_map(|line| line.split_whitespace()) {
+            self.tree.insert(value.to_string());
+        }
+        Vec::new()
+    }
+
+    fn save(&self, _file: &str) -> String {
+        self.tree.collect_inorder().iter().map(|value| format!("{} ", value)).collect()
+    }
+
+    fn snapshot(&self) -> Option<String> {
+        Some(self.restore_command())
+    }
+
+    fn restore(&mut self, _target: &str, values: &[&str]) -> Result<(), String> {
+        self.tree = values.iter().map(|value| value.to_string()).collect();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{printed, run, structures};
+
+    #[test]
+    fn tsize_follows_inserts_and_undo() {
+        let mut ds = structures();
+        assert_eq!(printed(&mut ds, "TSIZE"), "Tree size: 0");
+        run(&mut ds, &["TINSERT a", "TINSERT b", "TINSERT c"]);
+        assert_eq!(printed(&mut ds, "TSIZE"), "Tree size: 3");
+        run(&mut ds, &["UNDO"]);
+        assert_eq!(printed(&mut ds, "TSIZE"), "Tree size: 2");
+    }
+}
@@ -0,0 +1,115 @@
+use crate::stack::Stack;
+
+// Очередь на двух стеках: элементы добавляются во входной стек,
+// а извлекаются из выходного. Когда выходной пуст, в него перекладывается
+// весь входной, поэтому извлечение выполняется за амортизированное O(1).
+pub struct TwoStackQueue {
+    inbox: Stack,
+    // Вершина выходного стека — начало очереди
+    outbox: Stack,
+}
+
+impl TwoStackQueue {
+    pub fn new() -> Self {
+        TwoStackQueue {
+            inbox: Stack::new(16),
+            outbox: Stack::new(16),
+        }
+    }
+
+    pub fn enqueue(&mut self, value: String) {
+        self.inbox.push(value).expect("unbounded stack");
+    }
+
+    pub fn dequeue(&mut self) -> Result<String, String> {
+        if self.outbox.is_empty() {
+            while let Ok(value) = self.inbox.pop() {
+                self.outbox.push(value).expect("unbounded stack");
+            }
+        }
+        self.outbox.pop().map_err(|_| "Queue is empty".to_string())
+    }
+
+    // Если выходной стек пуст, начало очереди — дно входного
+    pub fn peek(&self) -> Result<&String, String> {
+        self.outbox
+            .peek()
+            .ok()
+            .or_else(|| self.inbox.iter().next_back())
+            .ok_or_else(|| "Queue is empty".to_string())
+    }
+
+    pub fn len(&self) -> usize {
+        self.inbox.len() + self.outbox.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inbox.is_empty() && self.outbox.is_empty()
+    }
+
+    // От первого элемента к последнему
+    pub fn iter(&self) -> impl Iterator<Item = &String> {
+        self.outbox.iter().chain(self.inbox.iter().rev())
+    }
+}
+
+impl Default for TwoStackQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TwoStackQueue;
+
+    fn values(queue: &TwoStackQueue) -> Vec<&str> {
+        queue.iter().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn keeps_fifo_order_across_both_stacks() {
+        let mut queue = TwoStackQueue::new();
+        for value in ["a", "b", "c"] {
+            queue.enqueue(value.to_string());
+        }
+        assert_eq!(queue.peek().unwrap(), "a");
+        assert_eq!(queue.dequeue().unwrap(), "a");
+        // b и c теперь в выходном стеке, d — во входном
+        queue.enqueue("d".to_string());
+        assert_eq!(values(&queue), ["b", "c", "d"]);
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.peek().unwrap(), "b");
+
+        let drained: Vec<String> = std::iter::from_fn(|| queue.dequeue().ok()).collect();
+        assert_eq!(drained, ["b", "c", "d"]);
+    }
+
+    #[test]
+    fn empty_queue_reports_errors() {
+        let mut queue = TwoStackQueue::default();
+        assert!(queue.is_empty());
+        assert_eq!(queue.dequeue(), Err("Queue is empty".to_string()));
+        assert_eq!(queue.peek(), Err("Queue is empty".to_string()));
+        queue.enqueue("a".to_string());
+        assert!(!queue.is_empty());
+        queue.dequeue().unwrap();
+        assert!(queue.is_empty());
+        assert_eq!(values(&queue), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn grows_past_initial_stack_capacity() {
+        let mut queue = TwoStackQueue::new();
+        for i in 0..40 {
+            queue.enqueue(i.to_string());
+        }
+        assert_eq!(queue.dequeue().unwrap(), "0");
+        for i in 40..60 {
+            queue.enqueue(i.to_string());
+        }
+        let expected: Vec<String> = (1..60).map(|i| i.to_string()).collect();
+        assert_eq!(queue.iter().cloned().collect::<Vec<_>>(), expected);
+        assert_eq!(queue.len(), 59);
+    }
+}